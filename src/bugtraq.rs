@@ -0,0 +1,278 @@
+use crate::{
+    i18n::{tr, trf},
+    svn::SvnProperty,
+};
+use regex::Regex;
+use std::ops::Range;
+
+/// Placeholder for the issue ID in `bugtraq:url` and `bugtraq:message`
+const BUGID: &str = "%BUGID%";
+
+/// Issue references found in a log message
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueRef {
+    /// Byte range of the ID in the message
+    pub range: Range<usize>,
+    pub id: String,
+}
+
+#[derive(Debug)]
+enum LogRegex {
+    /// One line: the groups of each match are the IDs
+    Single(Regex),
+    /// Two lines: the first finds the reference, the second the IDs inside it
+    Double(Regex, Regex),
+}
+
+/// Issue tracker settings read from the `bugtraq:*` properties, as TortoiseSVN
+/// uses them
+#[derive(Debug)]
+pub struct Bugtraq {
+    /// Name of the issue field, `bugtraq:label`
+    pub label: String,
+    url: Option<String>,
+    message: Option<String>,
+    /// The ID only takes digits and commas, `bugtraq:number` (true by default)
+    pub number: bool,
+    /// Add the issue line after the message instead of before it
+    append: bool,
+    /// Ask before committing without an issue, `bugtraq:warnifnoissue`
+    pub warn_if_no_issue: bool,
+    log_regex: Option<LogRegex>,
+}
+
+fn is_true(value: &str) -> bool {
+    matches!(value.trim().to_lowercase().as_str(), "true" | "yes" | "1")
+}
+
+impl Bugtraq {
+    /// `None` when the properties configure no issue tracker. Properties further
+    /// down the list win, so inherited values come first.
+    pub fn from_properties(properties: &[SvnProperty]) -> (Option<Self>, Vec<String>) {
+        let value = |name: &str| {
+            properties
+                .iter()
+                .rev()
+                .find(|property| property.name == name)
+                .map(|property| property.value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let message = value("bugtraq:message").filter(|message| message.contains(BUGID));
+        let logregex = value("bugtraq:logregex");
+        if message.is_none() && logregex.is_none() {
+            return (None, Vec::new());
+        }
+        let mut errors = Vec::new();
+        let mut compile = |pattern: &str| {
+            Regex::new(pattern)
+                .map_err(|e| errors.push(trf("bugtraq.invalid_regex", &[&pattern, &e])))
+                .ok()
+        };
+        let number = value("bugtraq:number").is_none_or(|value| is_true(&value));
+        let log_regex = match &logregex {
+            Some(patterns) => {
+                let mut lines = patterns.lines().map(str::trim).filter(|l| !l.is_empty());
+                match (lines.next(), lines.next()) {
+                    (Some(find), Some(id)) => compile(find)
+                        .zip(compile(id))
+                        .map(|(find, id)| LogRegex::Double(find, id)),
+                    (Some(find), None) => compile(find).map(LogRegex::Single),
+                    _ => None,
+                }
+            }
+            // Without a regex the references look like the message template
+            None => message.as_deref().and_then(|message| {
+                let id = if number { r"(\d+)" } else { r"(\S+)" };
+                compile(&regex::escape(message).replace(BUGID, id)).map(LogRegex::Single)
+            }),
+        };
+        let bugtraq = Bugtraq {
+            label: value("bugtraq:label").unwrap_or_else(|| tr("bugtraq.label").to_string()),
+            url: value("bugtraq:url"),
+            message,
+            number,
+            append: value("bugtraq:append").is_none_or(|value| is_true(&value)),
+            warn_if_no_issue: value("bugtraq:warnifnoissue").is_some_and(|value| is_true(&value)),
+            log_regex,
+        };
+        (Some(bugtraq), errors)
+    }
+
+    /// Whether the issue field has room for the character
+    pub fn accepts(&self, c: char) -> bool {
+        !self.number || c.is_ascii_digit() || c == ','
+    }
+
+    /// The message as it is committed, with the issue line from
+    /// `bugtraq:message` added when an ID was given
+    pub fn apply(&self, message: &str, issue: &str) -> String {
+        let issue = issue.trim();
+        let Some(template) = self.message.as_deref().filter(|_| !issue.is_empty()) else {
+            return message.to_string();
+        };
+        let line = template.replace(BUGID, issue);
+        if message.trim().is_empty() {
+            line
+        } else if self.append {
+            format!("{}\n{}", message.trim_end(), line)
+        } else {
+            format!("{}\n{}", line, message)
+        }
+    }
+
+    /// True when the commit names an issue, in the field or in the message
+    pub fn has_issue(&self, message: &str, issue: &str) -> bool {
+        !issue.trim().is_empty() || !self.issue_refs(message).is_empty()
+    }
+
+    pub fn issue_refs(&self, text: &str) -> Vec<IssueRef> {
+        let reference = |range: Range<usize>| IssueRef {
+            id: text[range.clone()].to_string(),
+            range,
+        };
+        match &self.log_regex {
+            None => Vec::new(),
+            Some(LogRegex::Single(regex)) => regex
+                .captures_iter(text)
+                .flat_map(|captures| {
+                    let groups: Vec<Range<usize>> = captures
+                        .iter()
+                        .skip(1)
+                        .flatten()
+                        .map(|group| group.range())
+                        .collect();
+                    if groups.is_empty() {
+                        vec![captures.get(0).map_or(0..0, |whole| whole.range())]
+                    } else {
+                        groups
+                    }
+                })
+                .filter(|range| !range.is_empty())
+                .map(reference)
+                .collect(),
+            Some(LogRegex::Double(find, id)) => find
+                .find_iter(text)
+                .flat_map(|found| {
+                    id.find_iter(found.as_str())
+                        .map(move |m| found.start() + m.start()..found.start() + m.end())
+                })
+                .map(reference)
+                .collect(),
+        }
+    }
+
+    /// Link to the issue in the tracker, from `bugtraq:url`
+    pub fn issue_url(&self, id: &str) -> Option<String> {
+        self.url.as_ref().map(|url| url.replace(BUGID, id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bugtraq(properties: &[(&str, &str)]) -> Bugtraq {
+        let properties: Vec<SvnProperty> = properties
+            .iter()
+            .map(|(name, value)| SvnProperty {
+                name: name.to_string(),
+                value: value.to_string(),
+            })
+            .collect();
+        let (bugtraq, errors) = Bugtraq::from_properties(&properties);
+        assert!(errors.is_empty(), "{:?}", errors);
+        bugtraq.expect("no issue tracker configured")
+    }
+
+    fn ids(refs: &[IssueRef]) -> Vec<&str> {
+        refs.iter().map(|issue| issue.id.as_str()).collect()
+    }
+
+    #[test]
+    fn from_properties_needs_a_message_or_regex() {
+        let properties = vec![SvnProperty {
+            name: "bugtraq:url".to_string(),
+            value: "https://tracker/%BUGID%".to_string(),
+        }];
+        assert!(Bugtraq::from_properties(&properties).0.is_none());
+        // A message without the placeholder is no template
+        let properties = vec![SvnProperty {
+            name: "bugtraq:message".to_string(),
+            value: "Issue".to_string(),
+        }];
+        assert!(Bugtraq::from_properties(&properties).0.is_none());
+    }
+
+    #[test]
+    fn from_properties_reads_settings_and_later_values_win() {
+        let bugtraq = bugtraq(&[
+            ("bugtraq:message", "Issue: %BUGID%"),
+            ("bugtraq:label", "Ticket"),
+            ("bugtraq:warnifnoissue", "yes"),
+            ("bugtraq:number", "false"),
+            ("bugtraq:label", "Bug"),
+            ("bugtraq:url", "https://tracker/show/%BUGID%"),
+        ]);
+        assert_eq!(bugtraq.label, "Bug");
+        assert!(bugtraq.warn_if_no_issue);
+        assert!(!bugtraq.number);
+        assert!(bugtraq.accepts('x'));
+        assert_eq!(
+            bugtraq.issue_url("AB-1"),
+            Some("https://tracker/show/AB-1".to_string())
+        );
+    }
+
+    #[test]
+    fn from_properties_reports_invalid_regexes() {
+        let properties = vec![SvnProperty {
+            name: "bugtraq:logregex".to_string(),
+            value: "(".to_string(),
+        }];
+        let (bugtraq, errors) = Bugtraq::from_properties(&properties);
+        assert_eq!(errors.len(), 1);
+        assert!(bugtraq.unwrap().issue_refs("#1").is_empty());
+    }
+
+    #[test]
+    fn apply_appends_by_default() {
+        let bugtraq = bugtraq(&[("bugtraq:message", "Issue: %BUGID%")]);
+        assert!(bugtraq.number);
+        assert!(!bugtraq.accepts('x'));
+        assert_eq!(bugtraq.apply("Fix it\n", "12"), "Fix it\nIssue: 12");
+        assert_eq!(bugtraq.apply("", "12"), "Issue: 12");
+        assert_eq!(bugtraq.apply("Fix it", "  "), "Fix it");
+    }
+
+    #[test]
+    fn apply_prepends_when_append_is_off() {
+        let bugtraq = bugtraq(&[
+            ("bugtraq:message", "[%BUGID%]"),
+            ("bugtraq:append", "false"),
+        ]);
+        assert_eq!(bugtraq.apply("Fix it", "12,13"), "[12,13]\nFix it");
+    }
+
+    #[test]
+    fn issue_refs_follow_the_message_template() {
+        let bugtraq = bugtraq(&[("bugtraq:message", "Issue: %BUGID%")]);
+        let refs = bugtraq.issue_refs("Fix\nIssue: 42 and Issue: x");
+        assert_eq!(ids(&refs), vec!["42"]);
+        assert_eq!(refs[0].range, 11..13);
+        assert!(bugtraq.has_issue("Fix\nIssue: 42", ""));
+        assert!(bugtraq.has_issue("Fix", "7"));
+        assert!(!bugtraq.has_issue("Fix", ""));
+    }
+
+    #[test]
+    fn issue_refs_use_one_or_two_regexes() {
+        let single = bugtraq(&[("bugtraq:logregex", r"#(\d+)")]);
+        assert_eq!(ids(&single.issue_refs("#1, #22 and #x")), vec!["1", "22"]);
+
+        let double = bugtraq(&[("bugtraq:logregex", "[Ii]ssues? #\\d+(, #\\d+)*\n\\d+")]);
+        let text = "Fixes issues #3, #45. See #6";
+        let refs = double.issue_refs(text);
+        assert_eq!(ids(&refs), vec!["3", "45"]);
+        assert_eq!(&text[refs[1].range.clone()], "45");
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    checks::ChecksConfig, hooks::HooksConfig, i18n::trf, message::CommitConfig, theme::ThemeConfig,
+    tools::ToolsConfig, watcher::WatchConfig,
+};
+use serde::Deserialize;
+use std::{collections::HashMap, env, ffi::OsString, fs, path::PathBuf};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> Vec<&str> {
+        match self {
+            KeyList::One(key) => vec![key.as_str()],
+            KeyList::Many(keys) => keys.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Action name to keys, per mode section (`[keys.normal]`, `[keys.commit]`, ...)
+pub type KeysConfig = HashMap<String, HashMap<String, KeyList>>;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub language: Option<String>,
+    /// Clicks and the wheel; off leaves text selection to the terminal
+    pub mouse: Option<bool>,
+    pub keys: KeysConfig,
+    pub theme: ThemeConfig,
+    pub checks: ChecksConfig,
+    pub hooks: HooksConfig,
+    pub commit: CommitConfig,
+    pub watch: WatchConfig,
+    pub tools: ToolsConfig,
+}
+
+/// The `rsvn` directory in the XDG directory when it is set, or in `default`
+/// under the home directory
+fn xdg_dir(xdg: Option<OsString>, home: Option<OsString>, default: &str) -> Option<PathBuf> {
+    xdg.filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home.map(|home| PathBuf::from(home).join(default)))
+        .map(|dir| dir.join("rsvn"))
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir(
+        env::var_os("XDG_CONFIG_HOME"),
+        env::var_os("HOME"),
+        ".config",
+    )
+}
+
+/// Where rsvn keeps data between runs, like the commit message history
+pub fn state_dir() -> Option<PathBuf> {
+    xdg_dir(
+        env::var_os("XDG_STATE_HOME"),
+        env::var_os("HOME"),
+        ".local/state",
+    )
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+pub fn load_config() -> Result<Config, String> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| trf("config.read_failed", &[&path.display(), &e]))?;
+    toml::from_str(&content).map_err(|e| trf("config.parse_failed", &[&path.display(), &e]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xdg_dir_prefers_the_xdg_variable() {
+        let home = Some(OsString::from("/home/ana"));
+        assert_eq!(
+            xdg_dir(Some("/var/state".into()), home.clone(), ".local/state"),
+            Some(PathBuf::from("/var/state/rsvn"))
+        );
+        assert_eq!(
+            xdg_dir(Some("".into()), home.clone(), ".local/state"),
+            Some(PathBuf::from("/home/ana/.local/state/rsvn"))
+        );
+        assert_eq!(
+            xdg_dir(None, home, ".local/state"),
+            Some(PathBuf::from("/home/ana/.local/state/rsvn"))
+        );
+        assert_eq!(xdg_dir(None, None, ".local/state"), None);
+    }
+}
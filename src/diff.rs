@@ -0,0 +1,402 @@
+use std::{collections::HashSet, ops::Range};
+
+/// Token pairs compared per line; longer lines are marked as changed whole
+const MAX_WORD_DIFF: usize = 250_000;
+const TAB: &str = "    ";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Header,
+    Hunk,
+    Added,
+    Removed,
+    Context,
+    /// `Property changes on:` sections and their `Added:`/`Modified:` lines
+    Property,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// Whitespace handling asked to `svn diff -x`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiffOptions {
+    /// `-w`: changes in the amount of whitespace are not differences
+    pub ignore_whitespace: bool,
+    /// `--ignore-eol-style`: CRLF against LF is not a difference
+    pub ignore_eol_style: bool,
+}
+
+impl DiffOptions {
+    /// Argument for `svn diff -x`, `None` when every option is off. svn only
+    /// keeps the last `-x`, so the options go together in one argument.
+    pub fn extensions(&self) -> Option<String> {
+        let options: Vec<&str> = [
+            (self.ignore_whitespace, "-w"),
+            (self.ignore_eol_style, "--ignore-eol-style"),
+        ]
+        .into_iter()
+        .filter_map(|(on, option)| on.then_some(option))
+        .collect();
+        (!options.is_empty()).then(|| options.join(" "))
+    }
+}
+
+/// One side of a side-by-side row
+#[derive(Debug, Clone, PartialEq)]
+pub struct SideCell {
+    /// `Removed` or `Added` for changed lines, `Context` otherwise
+    pub kind: DiffKind,
+    pub number: usize,
+    pub text: String,
+    /// Byte ranges of `text` that differ from the other side of the row
+    pub changes: Vec<Range<usize>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SideRow {
+    /// Headers, hunks and properties take both columns
+    Full(DiffLine),
+    /// Old file on the left, new file on the right; a missing side is a line
+    /// that only exists in the other file
+    Pair {
+        left: Option<SideCell>,
+        right: Option<SideCell>,
+    },
+}
+
+/// Classifies the lines of `svn diff` output, content and property changes alike.
+/// `---`/`+++` are file headers only before the first hunk of a file; inside
+/// a hunk they are a removed `-- ...` or an added `++ ...` line.
+pub fn parse_diff(text: &str) -> Vec<DiffLine> {
+    let mut in_properties = false;
+    // The output starts with a file header
+    let mut in_header = true;
+    text.lines()
+        .map(|line| {
+            let kind = if line.starts_with("Index: ") {
+                in_properties = false;
+                in_header = true;
+                DiffKind::Header
+            } else if line.starts_with("Property changes on: ") {
+                in_properties = true;
+                DiffKind::Property
+            } else if in_properties
+                && (line.starts_with("___")
+                    || line.starts_with("Added: ")
+                    || line.starts_with("Modified: ")
+                    || line.starts_with("Deleted: "))
+            {
+                DiffKind::Property
+            } else if line.starts_with("@@") || line.starts_with("##") {
+                in_header = false;
+                DiffKind::Hunk
+            } else if !in_properties && line.starts_with("===") {
+                in_header = true;
+                DiffKind::Header
+            } else if in_header && (line.starts_with("--- ") || line.starts_with("+++ ")) {
+                DiffKind::Header
+            } else if line.starts_with('+') {
+                DiffKind::Added
+            } else if line.starts_with('-') {
+                DiffKind::Removed
+            } else {
+                DiffKind::Context
+            };
+            DiffLine {
+                kind,
+                text: line.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Lays the parsed diff out in two columns. Removed and added lines that
+/// follow each other are paired up and compared word by word.
+pub fn side_by_side(lines: &[DiffLine]) -> Vec<SideRow> {
+    let mut rows = Vec::new();
+    let (mut old, mut new) = (0, 0);
+    let mut removed: Vec<SideCell> = Vec::new();
+    let mut added: Vec<SideCell> = Vec::new();
+    let cell = |kind: DiffKind, number: usize, text: &str| SideCell {
+        kind,
+        number,
+        text: text.get(1..).unwrap_or_default().replace('\t', TAB),
+        changes: Vec::new(),
+    };
+    for line in lines {
+        match line.kind {
+            DiffKind::Removed => {
+                removed.push(cell(line.kind, old, &line.text));
+                old += 1;
+                continue;
+            }
+            DiffKind::Added => {
+                added.push(cell(line.kind, new, &line.text));
+                new += 1;
+                continue;
+            }
+            _ => {}
+        }
+        pair_changes(&mut rows, &mut removed, &mut added);
+        match line.kind {
+            // `\ No newline at end of file` belongs to no side
+            DiffKind::Context if !line.text.starts_with('\\') => {
+                rows.push(SideRow::Pair {
+                    left: Some(cell(line.kind, old, &line.text)),
+                    right: Some(cell(line.kind, new, &line.text)),
+                });
+                old += 1;
+                new += 1;
+            }
+            _ => {
+                if line.kind == DiffKind::Hunk
+                    && let Some((old_start, new_start)) = hunk_starts(&line.text)
+                {
+                    (old, new) = (old_start, new_start);
+                }
+                rows.push(SideRow::Full(line.clone()));
+            }
+        }
+    }
+    pair_changes(&mut rows, &mut removed, &mut added);
+    rows
+}
+
+/// Numbers in the new file of the lines the diff adds
+pub fn added_lines(lines: &[DiffLine]) -> HashSet<usize> {
+    side_by_side(lines)
+        .into_iter()
+        .filter_map(|row| match row {
+            SideRow::Pair {
+                right: Some(cell), ..
+            } if cell.kind == DiffKind::Added => Some(cell.number),
+            _ => None,
+        })
+        .collect()
+}
+
+/// First line numbers of `@@ -12,7 +12,8 @@`, and of `## -1 +1 ##` in properties
+fn hunk_starts(text: &str) -> Option<(usize, usize)> {
+    let mut ranges = text.split_whitespace().skip(1);
+    let start = |range: Option<&str>, sign: char| {
+        range?
+            .strip_prefix(sign)?
+            .split(',')
+            .next()?
+            .parse::<usize>()
+            .ok()
+    };
+    Some((start(ranges.next(), '-')?, start(ranges.next(), '+')?))
+}
+
+fn pair_changes(rows: &mut Vec<SideRow>, removed: &mut Vec<SideCell>, added: &mut Vec<SideCell>) {
+    let count = removed.len().max(added.len());
+    let mut removed = removed.drain(..);
+    let mut added = added.drain(..);
+    for _ in 0..count {
+        let mut left = removed.next();
+        let mut right = added.next();
+        if let (Some(left), Some(right)) = (&mut left, &mut right) {
+            (left.changes, right.changes) = word_changes(&left.text, &right.text);
+        }
+        rows.push(SideRow::Pair { left, right });
+    }
+}
+
+/// Words, runs of spaces and single symbols
+fn tokens(text: &str) -> Vec<Range<usize>> {
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut tokens: Vec<Range<usize>> = Vec::new();
+    let mut previous = None;
+    for (idx, c) in text.char_indices() {
+        let kind = class(c);
+        match tokens.last_mut() {
+            Some(last) if kind != 2 && previous == Some(kind) => last.end = idx + c.len_utf8(),
+            _ => tokens.push(idx..idx + c.len_utf8()),
+        }
+        previous = Some(kind);
+    }
+    tokens
+}
+
+/// Ranges of each line left out of their longest common token sequence
+fn word_changes(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let (old_tokens, new_tokens) = (tokens(old), tokens(new));
+    let whole = |text: &str| -> Vec<Range<usize>> {
+        (!text.is_empty())
+            .then_some(0..text.len())
+            .into_iter()
+            .collect()
+    };
+    if old_tokens.len() * new_tokens.len() > MAX_WORD_DIFF {
+        return (whole(old), whole(new));
+    }
+    let (rows, columns) = (old_tokens.len(), new_tokens.len());
+    // common[i][j]: common tokens of old_tokens[i..] and new_tokens[j..]
+    let mut common = vec![vec![0u32; columns + 1]; rows + 1];
+    for i in (0..rows).rev() {
+        for j in (0..columns).rev() {
+            common[i][j] = if old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let (mut old_changes, mut new_changes) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < rows || j < columns {
+        if i < rows && j < columns && old[old_tokens[i].clone()] == new[new_tokens[j].clone()] {
+            i += 1;
+            j += 1;
+        } else if j == columns || (i < rows && common[i + 1][j] >= common[i][j + 1]) {
+            push_range(&mut old_changes, old_tokens[i].clone());
+            i += 1;
+        } else {
+            push_range(&mut new_changes, new_tokens[j].clone());
+            j += 1;
+        }
+    }
+    (old_changes, new_changes)
+}
+
+/// Joins touching ranges so a changed phrase is highlighted as one
+fn push_range(ranges: &mut Vec<Range<usize>>, range: Range<usize>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HUNK_WITH_DASHES: &str = "Index: q.sql
+===================================================================
+--- q.sql\t(revision 7)
++++ q.sql\t(working copy)
+@@ -1,3 +1,3 @@
+ select 1;
+--- x
++++ y
+ select 2;
+";
+
+    fn kinds(lines: &[DiffLine]) -> Vec<DiffKind> {
+        lines.iter().map(|line| line.kind).collect()
+    }
+
+    #[test]
+    fn parse_diff_keeps_dash_lines_inside_hunks() {
+        use DiffKind::*;
+        assert_eq!(
+            kinds(&parse_diff(HUNK_WITH_DASHES)),
+            vec![
+                Header, Header, Header, Header, Hunk, Context, Removed, Added, Context
+            ]
+        );
+    }
+
+    #[test]
+    fn side_by_side_numbers_dash_lines_inside_hunks() {
+        let rows = side_by_side(&parse_diff(HUNK_WITH_DASHES));
+        assert_eq!(rows.len(), 8);
+        assert_eq!(
+            numbers(&rows),
+            vec![
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some((Some(1), Some(1))),
+                Some((Some(2), Some(2))),
+                Some((Some(3), Some(3))),
+            ]
+        );
+        let SideRow::Pair {
+            left: Some(left),
+            right: Some(right),
+        } = &rows[6]
+        else {
+            panic!("the changed lines are not paired: {:?}", rows[6]);
+        };
+        assert_eq!((left.text.as_str(), right.text.as_str()), ("-- x", "++ y"));
+    }
+
+    /// Line numbers of each pair row, `None` for full-width rows
+    fn numbers(rows: &[SideRow]) -> Vec<Option<(Option<usize>, Option<usize>)>> {
+        rows.iter()
+            .map(|row| match row {
+                SideRow::Full(_) => None,
+                SideRow::Pair { left, right } => Some((
+                    left.as_ref().map(|cell| cell.number),
+                    right.as_ref().map(|cell| cell.number),
+                )),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn side_by_side_leaves_unmatched_lines_alone() {
+        let diff =
+            "@@ -10,2 +10,3 @@\n-old\n+new\n+extra\n context\n\\ No newline at end of file\n";
+        let rows = side_by_side(&parse_diff(diff));
+        assert_eq!(
+            numbers(&rows),
+            vec![
+                None,
+                Some((Some(10), Some(10))),
+                Some((None, Some(11))),
+                Some((Some(11), Some(12))),
+                None,
+            ]
+        );
+    }
+
+    type Spans = Vec<(usize, usize)>;
+
+    /// Changed ranges of both lines as `(start, end)` pairs
+    fn changes(old: &str, new: &str) -> (Spans, Spans) {
+        let pairs = |ranges: Vec<Range<usize>>| ranges.iter().map(|r| (r.start, r.end)).collect();
+        let (old, new) = word_changes(old, new);
+        (pairs(old), pairs(new))
+    }
+
+    #[test]
+    fn word_changes_marks_differing_words() {
+        assert_eq!(
+            changes("let total = a + b;", "let sum = a + b;"),
+            (vec![(4, 9)], vec![(4, 7)])
+        );
+        assert_eq!(changes("call(a)", "call(a, b)"), (vec![], vec![(6, 9)]));
+        assert_eq!(changes("same", "same"), (vec![], vec![]));
+    }
+
+    #[test]
+    fn word_changes_joins_touching_tokens() {
+        assert_eq!(changes("x = a.b", "x = c::d"), (vec![(4, 7)], vec![(4, 8)]));
+        assert_eq!(changes("", "added"), (vec![], vec![(0, 5)]));
+    }
+
+    #[test]
+    fn added_lines_numbers_the_new_file() {
+        let diff = "@@ -1,3 +1,4 @@\n a\n-b\n+B\n+C\n c\n@@ -10,2 +11,2 @@\n x\n+y\n-z\n";
+        let mut lines: Vec<usize> = added_lines(&parse_diff(diff)).into_iter().collect();
+        lines.sort();
+        assert_eq!(lines, vec![2, 3, 12]);
+    }
+}
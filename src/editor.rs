@@ -0,0 +1,36 @@
+use crate::i18n::trf;
+use std::{env, fs, io::Write, process::Command};
+
+/// Opens `$VISUAL`, `$EDITOR` or `vi` on a temporary file holding `initial` and
+/// returns the saved text without trailing newlines. The caller must leave the
+/// alternate screen before and restore it afterwards.
+pub fn edit_text(initial: &str) -> Result<String, String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    // A fresh name, created only if nothing is there yet, so a file or link
+    // someone else left in the temp dir cannot take the text
+    let mut file = tempfile::Builder::new()
+        .prefix("rsvn-edit-")
+        .suffix(".txt")
+        .tempfile()
+        .map_err(|e| trf("editor.write_failed", &[&e]))?;
+    file.write_all(initial.as_bytes())
+        .and_then(|_| file.flush())
+        .map_err(|e| trf("editor.write_failed", &[&e]))?;
+    let path = file.path().to_path_buf();
+    // Editors are often configured with arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = Command::new(program).args(words).arg(&path).status();
+    // `file` is removed when it goes out of scope, after the text is read
+    match status {
+        Ok(status) if status.success() => fs::read_to_string(&path)
+            .map(|text| text.trim_end_matches(['\n', '\r']).to_string())
+            .map_err(|e| trf("editor.read_failed", &[&e])),
+        Ok(status) => Err(trf("editor.exit_status", &[&editor, &status])),
+        Err(e) => Err(trf("editor.launch_failed", &[&editor, &e])),
+    }
+}
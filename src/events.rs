@@ -0,0 +1,207 @@
+use crate::{
+    i18n::tr,
+    preview::Preview,
+    svn::{ChangedPath, CompareTarget, LockInfo, LogEntry, SvnStatusEntry},
+};
+use crossterm::event::{self, Event};
+use std::{
+    io,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError},
+    thread,
+    time::Duration,
+};
+
+/// How long the input thread waits for a key before looking for a pause
+/// request again, the longest `InputReader::pause` can take
+const INPUT_CHECK: Duration = Duration::from_millis(100);
+
+/// Work that runs on its own thread so the screen keeps drawing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JobKind {
+    Status,
+    Log,
+    Lock,
+    Preview,
+    Compare,
+}
+
+impl JobKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobKind::Status => tr("job.status"),
+            JobKind::Log => tr("job.log"),
+            JobKind::Lock => tr("job.lock"),
+            JobKind::Preview => tr("job.preview"),
+            JobKind::Compare => tr("job.compare"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum JobResult {
+    /// `generation` is the client's refresh count when the job started, a
+    /// refresh done meanwhile makes these entries the older ones. `remote`
+    /// tells whether the job asked the repository (`svn status -u`).
+    Status {
+        generation: u64,
+        remote: bool,
+        entries: Result<Vec<SvnStatusEntry>, String>,
+    },
+    Log(Result<Vec<LogEntry>, String>),
+    /// Lock details of `file`, asked while the status had `generation`
+    Lock {
+        generation: u64,
+        file: PathBuf,
+        info: Option<LockInfo>,
+    },
+    Preview {
+        file: PathBuf,
+        preview: Result<Preview, String>,
+    },
+    Compare {
+        target: CompareTarget,
+        paths: Result<Vec<ChangedPath>, String>,
+    },
+}
+
+impl JobResult {
+    pub fn kind(&self) -> JobKind {
+        match self {
+            JobResult::Status { .. } => JobKind::Status,
+            JobResult::Log(_) => JobKind::Log,
+            JobResult::Lock { .. } => JobKind::Lock,
+            JobResult::Preview { .. } => JobKind::Preview,
+            JobResult::Compare { .. } => JobKind::Compare,
+        }
+    }
+}
+
+/// Everything the main loop wakes up for
+#[derive(Debug)]
+pub enum AppEvent {
+    Input(Event),
+    InputError(io::Error),
+    /// Nothing arrived within the tick interval; time to animate
+    Tick,
+    FilesChanged,
+    Job(JobResult),
+}
+
+/// The channel every thread sends its events to
+#[derive(Debug)]
+pub struct Events {
+    sender: Sender<AppEvent>,
+    receiver: Receiver<AppEvent>,
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Events { sender, receiver }
+    }
+}
+
+impl Events {
+    pub fn sender(&self) -> Sender<AppEvent> {
+        self.sender.clone()
+    }
+
+    /// Blocks until something happens, or until `tick` passes when given
+    pub fn next(&self, tick: Option<Duration>) -> AppEvent {
+        let received = match tick {
+            Some(tick) => self.receiver.recv_timeout(tick),
+            None => self
+                .receiver
+                .recv()
+                .map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        // The receiver owns a sender too, so the channel never disconnects
+        received.unwrap_or(AppEvent::Tick)
+    }
+}
+
+/// Requests from the main loop to the input thread
+#[derive(Debug)]
+enum Control {
+    /// Stop reading; the thread answers on the sender once it no longer will
+    Pause(Sender<()>),
+    Resume,
+}
+
+/// Reads the terminal on its own thread. Paused while an external program
+/// such as the editor owns the terminal, so no keys are stolen from it.
+#[derive(Debug)]
+pub struct InputReader {
+    control: Sender<Control>,
+}
+
+/// Acknowledges the pause and waits for the resume. False when the main loop
+/// is gone and the thread should end.
+fn wait_for_resume(commands: &Receiver<Control>, ack: Sender<()>) -> bool {
+    let _ = ack.send(());
+    loop {
+        match commands.recv() {
+            Ok(Control::Resume) => break,
+            Ok(Control::Pause(ack)) => {
+                let _ = ack.send(());
+            }
+            Err(_) => return false,
+        }
+    }
+    // Keys crossterm buffered during the pause were typed for the other program
+    while matches!(event::poll(Duration::ZERO), Ok(true)) {
+        if event::read().is_err() {
+            break;
+        }
+    }
+    true
+}
+
+impl InputReader {
+    pub fn start(sender: Sender<AppEvent>) -> Self {
+        let (control, commands) = mpsc::channel();
+        thread::spawn(move || {
+            loop {
+                let ready = event::poll(INPUT_CHECK);
+                // Checked between the poll and the read, so nothing is read
+                // once the pause is acknowledged
+                match commands.try_recv() {
+                    Ok(Control::Pause(ack)) => {
+                        if wait_for_resume(&commands, ack) {
+                            continue;
+                        }
+                        return;
+                    }
+                    Ok(Control::Resume) | Err(TryRecvError::Empty) => {}
+                    Err(TryRecvError::Disconnected) => return,
+                }
+                let event = match ready {
+                    Ok(false) => continue,
+                    Ok(true) => event::read().map(AppEvent::Input),
+                    Err(e) => Err(e),
+                };
+                let failed = event.is_err();
+                let event = event.unwrap_or_else(AppEvent::InputError);
+                if sender.send(event).is_err() || failed {
+                    return;
+                }
+            }
+        });
+        InputReader { control }
+    }
+
+    /// Returns once the thread has stopped reading, keys pressed from then on
+    /// stay in the terminal for the next program
+    pub fn pause(&self) {
+        let (ack, acked) = mpsc::channel();
+        if self.control.send(Control::Pause(ack)).is_ok() {
+            // An error means the thread already ended, it reads nothing either
+            let _ = acked.recv();
+        }
+    }
+
+    pub fn resume(&self) {
+        let _ = self.control.send(Control::Resume);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::svn::{SvnState, SvnStatusList};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Ndjson,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EntryExport {
+    pub file: String,
+    pub state: String,
+    pub kind: SvnState,
+    pub selected: bool,
+    pub changelist: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StatusExport {
+    pub entries: Vec<EntryExport>,
+    pub selections: Vec<String>,
+    pub changelists: BTreeMap<String, Vec<String>>,
+}
+
+impl StatusExport {
+    pub fn from_status(list: &SvnStatusList) -> Self {
+        let entries: Vec<EntryExport> = list
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| EntryExport {
+                file: entry.file.to_string_lossy().into_owned(),
+                state: entry.state.clone(),
+                kind: entry.kind(),
+                selected: list.selections.contains(&idx),
+                changelist: entry.changelist.clone(),
+            })
+            .collect();
+        let selections = entries
+            .iter()
+            .filter(|entry| entry.selected)
+            .map(|entry| entry.file.clone())
+            .collect();
+        let mut changelists: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for entry in &entries {
+            if let Some(name) = &entry.changelist {
+                changelists
+                    .entry(name.clone())
+                    .or_default()
+                    .push(entry.file.clone());
+            }
+        }
+        StatusExport {
+            entries,
+            selections,
+            changelists,
+        }
+    }
+}
+
+pub fn export_status(list: &SvnStatusList, format: ExportFormat) -> serde_json::Result<String> {
+    let export = StatusExport::from_status(list);
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(&export),
+        ExportFormat::Ndjson => {
+            let mut out = String::new();
+            for entry in &export.entries {
+                out.push_str(&serde_json::to_string(entry)?);
+                out.push('\n');
+            }
+            Ok(out)
+        }
+    }
+}
@@ -5,8 +5,12 @@ use std::time::Duration;
 
 pub fn copy_file(selected: usize, list: &[SvnStatusEntry]) -> Result<(), arboard::Error> {
     let file_selected = &list[selected].file;
+    copy_text(&file_selected.to_string_lossy())
+}
+
+pub fn copy_text(text: &str) -> Result<(), arboard::Error> {
     let mut clipboard = Clipboard::new()?;
-    clipboard.set_text(file_selected.to_string_lossy())?;
+    clipboard.set_text(text)?;
     thread::sleep(Duration::from_millis(50));
     Ok(())
 }
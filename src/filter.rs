@@ -0,0 +1,91 @@
+use crate::svn::SvnStatusEntry;
+
+/// An entry that passed the filter, with the char positions of its path that matched
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterMatch {
+    pub idx: usize,
+    pub positions: Vec<usize>,
+}
+
+/// Case-insensitive subsequence match, returning the matched char positions.
+/// Every pattern character must appear in the text in the same order.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<Vec<usize>> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let mut positions = Vec::with_capacity(pattern.len());
+    for (i, c) in text.chars().enumerate() {
+        match pattern.get(positions.len()) {
+            Some(wanted) if c.to_lowercase().eq(std::iter::once(*wanted)) => positions.push(i),
+            Some(_) => {}
+            None => break,
+        }
+    }
+    (positions.len() == pattern.len()).then_some(positions)
+}
+
+/// Entries whose path matches the pattern, in their original order
+pub fn filter_entries(entries: &[SvnStatusEntry], pattern: &str) -> Vec<FilterMatch> {
+    entries
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, entry)| {
+            fuzzy_match(pattern, &entry.file.to_string_lossy())
+                .map(|positions| FilterMatch { idx, positions })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::{StatusRow, flat_rows};
+    use std::path::PathBuf;
+
+    fn entries(files: &[&str]) -> Vec<SvnStatusEntry> {
+        files
+            .iter()
+            .map(|file| SvnStatusEntry::new(PathBuf::from(file), "M".to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn fuzzy_match_finds_an_ordered_subsequence() {
+        assert_eq!(fuzzy_match("smr", "src/main.rs"), Some(vec![0, 4, 9]));
+        assert_eq!(fuzzy_match("SMR", "src/main.rs"), Some(vec![0, 4, 9]));
+        assert_eq!(fuzzy_match("nm", "src/main.rs"), None);
+        assert_eq!(fuzzy_match("", "src/main.rs"), Some(vec![]));
+        assert_eq!(fuzzy_match("x", ""), None);
+    }
+
+    #[test]
+    fn fuzzy_match_takes_the_first_occurrences() {
+        assert_eq!(fuzzy_match("ab", "aabb"), Some(vec![0, 2]));
+        // Positions count chars, not bytes
+        assert_eq!(fuzzy_match("ño", "año.txt"), Some(vec![1, 2]));
+        assert_eq!(fuzzy_match("Ñ", "AÑO"), Some(vec![1]));
+    }
+
+    #[test]
+    fn filter_entries_keeps_the_list_order_and_indices() {
+        let entries = entries(&["README.md", "src/main.rs", "docs/manual.md", "src/lib.rs"]);
+        let matches = filter_entries(&entries, "ma");
+        let indices: Vec<usize> = matches.iter().map(|m| m.idx).collect();
+        assert_eq!(indices, vec![1, 2]);
+        assert_eq!(matches[1].positions, vec![5, 6]);
+        assert_eq!(filter_entries(&entries, "").len(), entries.len());
+        assert!(filter_entries(&entries, "zzz").is_empty());
+    }
+
+    #[test]
+    fn filtered_rows_point_at_the_source_entries() {
+        let entries = entries(&["a.txt", "b/lib.rs", "c.txt", "d/lib.rs"]);
+        let rows = flat_rows(&entries, &filter_entries(&entries, "lib"));
+        let targets: Vec<Option<usize>> = rows.iter().map(StatusRow::entry_idx).collect();
+        assert_eq!(targets, vec![Some(1), Some(3)]);
+        for row in &rows {
+            let StatusRow::Entry { idx, label, .. } = row else {
+                panic!("flat rows are entries: {:?}", row);
+            };
+            assert_eq!(PathBuf::from(label), entries[*idx].file);
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{config::state_dir, i18n::trf};
+use std::{fs, path::PathBuf};
+
+const MAX_MESSAGES: usize = 100;
+
+/// Past commit messages, newest first, saved as JSON under the state directory
+#[derive(Debug, Default)]
+pub struct MessageHistory {
+    messages: Vec<String>,
+    /// Message shown while browsing, `None` when editing the draft
+    position: Option<usize>,
+    draft: String,
+}
+
+fn history_path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("commit_history.json"))
+}
+
+impl MessageHistory {
+    /// A missing or unreadable history just starts empty
+    pub fn load() -> Self {
+        let messages = history_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        MessageHistory {
+            messages,
+            ..MessageHistory::default()
+        }
+    }
+
+    /// Moves the message to the top of the history and saves it
+    pub fn push(&mut self, message: &str) -> Result<(), String> {
+        if !self.remember(message) {
+            return Ok(());
+        }
+        let Some(path) = history_path() else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(&self.messages).map_err(|e| e.to_string())?;
+        path.parent()
+            .map(fs::create_dir_all)
+            .transpose()
+            .and_then(|_| fs::write(&path, json))
+            .map_err(|e| trf("history.write_failed", &[&path.display(), &e]))
+    }
+
+    /// Moves the message to the top, false for an empty message
+    fn remember(&mut self, message: &str) -> bool {
+        self.reset();
+        let message = message.trim_end();
+        if message.trim().is_empty() {
+            return false;
+        }
+        self.messages.retain(|m| m != message);
+        self.messages.insert(0, message.to_string());
+        self.messages.truncate(MAX_MESSAGES);
+        true
+    }
+
+    /// Older message; the first call keeps `current` as the draft to come back to
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        let next = match self.position {
+            None => 0,
+            Some(position) => position + 1,
+        };
+        if next >= self.messages.len() {
+            return None;
+        }
+        if self.position.is_none() {
+            self.draft = current.to_string();
+        }
+        self.position = Some(next);
+        self.messages.get(next).map(String::as_str)
+    }
+
+    /// Newer message, or the draft after the newest one
+    pub fn next(&mut self) -> Option<&str> {
+        match self.position? {
+            0 => {
+                self.position = None;
+                Some(&self.draft)
+            }
+            position => {
+                self.position = Some(position - 1);
+                self.messages.get(position - 1).map(String::as_str)
+            }
+        }
+    }
+
+    /// Typing over a recalled message makes it the new draft
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(messages: &[&str]) -> MessageHistory {
+        MessageHistory {
+            messages: messages.iter().map(|m| m.to_string()).collect(),
+            ..MessageHistory::default()
+        }
+    }
+
+    #[test]
+    fn browsing_returns_to_the_draft() {
+        let mut history = history(&["newest", "older"]);
+        assert_eq!(history.previous("draft"), Some("newest"));
+        assert_eq!(history.previous("newest"), Some("older"));
+        assert_eq!(history.previous("older"), None);
+        assert_eq!(history.next(), Some("newest"));
+        assert_eq!(history.next(), Some("draft"));
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn reset_keeps_browsing_from_the_newest() {
+        let mut history = history(&["newest", "older"]);
+        history.previous("first draft");
+        history.previous("newest");
+        history.reset();
+        assert_eq!(history.next(), None);
+        assert_eq!(history.previous("second draft"), Some("newest"));
+        assert_eq!(history.next(), Some("second draft"));
+    }
+
+    #[test]
+    fn empty_history_has_nothing_to_browse() {
+        let mut history = history(&[]);
+        assert_eq!(history.previous("draft"), None);
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn remember_moves_repeated_messages_to_the_top() {
+        let mut history = history(&["b", "a"]);
+        assert!(history.remember("a\n\n"));
+        assert_eq!(history.messages, vec!["a", "b"]);
+        assert!(!history.remember("  \n"));
+        assert_eq!(history.messages, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn remember_keeps_the_newest_messages() {
+        let mut history = history(&[]);
+        for i in 0..MAX_MESSAGES + 5 {
+            history.remember(&format!("message {}", i));
+        }
+        assert_eq!(history.messages.len(), MAX_MESSAGES);
+        assert_eq!(history.messages[0], format!("message {}", MAX_MESSAGES + 4));
+    }
+}
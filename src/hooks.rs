@@ -0,0 +1,249 @@
+use crate::i18n::trf;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread::{self, JoinHandle},
+};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum HookCommands {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl HookCommands {
+    fn commands(&self) -> Vec<String> {
+        match self {
+            HookCommands::One(command) => vec![command.clone()],
+            HookCommands::Many(commands) => commands.clone(),
+        }
+    }
+}
+
+/// `[hooks]` section: `pre_commit = "cargo fmt --check"`, `post_update = [...]`, ...
+pub type HooksConfig = HashMap<String, HookCommands>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookStage {
+    Pre,
+    Post,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookAction {
+    Commit,
+    Update,
+    Revert,
+    Add,
+}
+
+impl HookAction {
+    const ALL: [HookAction; 4] = [
+        HookAction::Commit,
+        HookAction::Update,
+        HookAction::Revert,
+        HookAction::Add,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HookAction::Commit => "commit",
+            HookAction::Update => "update",
+            HookAction::Revert => "revert",
+            HookAction::Add => "add",
+        }
+    }
+}
+
+fn hook_name(stage: HookStage, action: HookAction) -> String {
+    match stage {
+        HookStage::Pre => format!("pre_{}", action.name()),
+        HookStage::Post => format!("post_{}", action.name()),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Hooks {
+    commands: HashMap<(HookStage, HookAction), Vec<String>>,
+}
+
+impl Hooks {
+    pub fn from_config(config: &HooksConfig) -> (Self, Vec<String>) {
+        let mut hooks = Hooks::default();
+        let mut errors = Vec::new();
+        for (name, commands) in config {
+            let found = HookAction::ALL.into_iter().find_map(|action| {
+                [HookStage::Pre, HookStage::Post]
+                    .into_iter()
+                    .find(|stage| hook_name(*stage, action) == *name)
+                    .map(|stage| (stage, action))
+            });
+            match found {
+                Some(key) => {
+                    hooks.commands.insert(key, commands.commands());
+                }
+                None => errors.push(trf("hooks.unknown", &[name])),
+            }
+        }
+        (hooks, errors)
+    }
+
+    /// Runs the commands of the hook with `sh -c` in the working copy. They get
+    /// `RSVN_HOOK`, `RSVN_ACTION`, `RSVN_FILES` (one per line) and `RSVN_MESSAGE`,
+    /// and the file list on stdin. Stops at the first command that fails.
+    /// Returns what the commands printed.
+    pub fn run(
+        &self,
+        stage: HookStage,
+        action: HookAction,
+        working_copy: &Path,
+        files: &[PathBuf],
+        message: &str,
+    ) -> Result<String, String> {
+        let Some(commands) = self.commands.get(&(stage, action)) else {
+            return Ok(String::new());
+        };
+        let name = hook_name(stage, action);
+        let file_list: String = files
+            .iter()
+            .map(|file| format!("{}\n", file.display()))
+            .collect();
+        let mut printed = String::new();
+        for command in commands {
+            let child = Command::new("sh")
+                .arg("-c")
+                .arg(command)
+                .current_dir(working_copy)
+                .env("RSVN_HOOK", &name)
+                .env("RSVN_ACTION", action.name())
+                .env("RSVN_FILES", &file_list)
+                .env("RSVN_MESSAGE", message)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn();
+            let mut child = child.map_err(|e| trf("hooks.spawn_failed", &[&name, &command, &e]))?;
+            // Written from another thread while the output is read, a hook that
+            // prints before reading all of stdin would block both sides otherwise
+            let writer = child.stdin.take().map(|mut stdin| {
+                let files = file_list.clone();
+                thread::spawn(move || stdin.write_all(files.as_bytes()))
+            });
+            let output = child
+                .wait_with_output()
+                .map_err(|e| trf("hooks.spawn_failed", &[&name, &command, &e]))?;
+            // A hook that ignores stdin may exit before reading it
+            if let Some(Ok(Err(e))) = writer.map(JoinHandle::join)
+                && e.kind() != ErrorKind::BrokenPipe
+            {
+                return Err(trf("hooks.stdin_failed", &[&name, &command, &e]));
+            }
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            if !output.status.success() {
+                return Err(trf(
+                    "hooks.failed",
+                    &[&name, &command, &output.status, &text.trim_end()],
+                ));
+            }
+            printed.push_str(&text);
+        }
+        Ok(printed.trim_end().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hooks(name: &str, commands: HookCommands) -> Hooks {
+        let config = HooksConfig::from([(name.to_string(), commands)]);
+        let (hooks, errors) = Hooks::from_config(&config);
+        assert!(errors.is_empty(), "{:?}", errors);
+        hooks
+    }
+
+    fn files() -> Vec<PathBuf> {
+        vec![PathBuf::from("src/main.rs"), PathBuf::from("a b.txt")]
+    }
+
+    #[test]
+    fn hook_reads_files_on_stdin_and_environment() {
+        let hooks = hooks(
+            "pre_commit",
+            HookCommands::One(r#"cat; echo "$RSVN_HOOK $RSVN_ACTION $RSVN_MESSAGE""#.to_string()),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let output = hooks.run(
+            HookStage::Pre,
+            HookAction::Commit,
+            dir.path(),
+            &files(),
+            "Fix it",
+        );
+        assert_eq!(
+            output,
+            Ok("src/main.rs\na b.txt\npre_commit commit Fix it".to_string())
+        );
+    }
+
+    #[test]
+    fn hook_failure_stops_the_remaining_commands() {
+        let hooks = hooks(
+            "pre_update",
+            HookCommands::Many(vec![
+                "echo first".to_string(),
+                "echo broken >&2; exit 3".to_string(),
+                "touch ran".to_string(),
+            ]),
+        );
+        let dir = tempfile::tempdir().unwrap();
+        let result = hooks.run(HookStage::Pre, HookAction::Update, dir.path(), &files(), "");
+        let error = result.unwrap_err();
+        assert!(error.contains("echo broken >&2; exit 3"), "{}", error);
+        assert!(error.contains("broken"), "{}", error);
+        assert!(!error.contains("first"), "{}", error);
+        assert!(!dir.path().join("ran").exists());
+    }
+
+    #[test]
+    fn hook_that_ignores_stdin_still_passes() {
+        let hooks = hooks("post_add", HookCommands::One("true".to_string()));
+        let dir = tempfile::tempdir().unwrap();
+        // More than a pipe holds, yet small enough for RSVN_FILES
+        let many: Vec<PathBuf> = (0..6000)
+            .map(|i| PathBuf::from(format!("file-{}.txt", i)))
+            .collect();
+        let result = hooks.run(HookStage::Post, HookAction::Add, dir.path(), &many, "");
+        assert_eq!(result, Ok(String::new()));
+    }
+
+    #[test]
+    fn missing_and_unknown_hooks() {
+        let hooks = hooks("post_revert", HookCommands::One("exit 1".to_string()));
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            hooks.run(HookStage::Pre, HookAction::Revert, dir.path(), &files(), ""),
+            Ok(String::new())
+        );
+        assert!(
+            hooks
+                .run(
+                    HookStage::Post,
+                    HookAction::Revert,
+                    dir.path(),
+                    &files(),
+                    ""
+                )
+                .is_err()
+        );
+        let config =
+            HooksConfig::from([("pre_push".to_string(), HookCommands::One("true".into()))]);
+        let (_, errors) = Hooks::from_config(&config);
+        assert_eq!(errors, vec![trf("hooks.unknown", &[&"pre_push"])]);
+    }
+}
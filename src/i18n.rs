@@ -0,0 +1,1084 @@
+use std::{env, fmt::Display, sync::OnceLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En,
+    Es,
+}
+
+impl Lang {
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.to_lowercase();
+        if code.starts_with("es") {
+            Some(Lang::Es)
+        } else if code.starts_with("en") {
+            Some(Lang::En)
+        } else {
+            None
+        }
+    }
+
+    /// Same precedence as gettext: `LC_ALL`, then `LC_MESSAGES`, then `LANG`
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Lang::from_code(&value))
+            .unwrap_or(Lang::En)
+    }
+}
+
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/// Only the first call has effect; later calls keep the language already in use
+pub fn set_lang(lang: Lang) {
+    let _ = LANG.set(lang);
+}
+
+pub fn lang() -> Lang {
+    *LANG.get_or_init(Lang::from_env)
+}
+
+pub fn tr(key: &'static str) -> &'static str {
+    let catalog = match lang() {
+        Lang::En => EN,
+        Lang::Es => ES,
+    };
+    lookup(catalog, key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key)
+}
+
+/// Replaces each `{}` of the message with the next argument
+pub fn trf(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut out = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(key).split("{}").peekable();
+    while let Some(part) = parts.next() {
+        out.push_str(part);
+        if parts.peek().is_some()
+            && let Some(arg) = args.next()
+        {
+            out.push_str(&arg.to_string());
+        }
+    }
+    out
+}
+
+fn lookup(catalog: &[(&str, &'static str)], key: &str) -> Option<&'static str> {
+    catalog
+        .iter()
+        .find(|(entry, _)| *entry == key)
+        .map(|(_, text)| *text)
+}
+
+static EN: &[(&str, &str)] = &[
+    ("cli.about", "A TUI for Svn projects"),
+    (
+        "cli.long_about",
+        "
+Rustisvn
+
+A minimal and simple app for svn projects.
+
+Available features:
+- Select files
+- Commit
+- Copy the file path
+- Export the status as JSON/NDJSON
+
+Everything you need for a simple workflow on trunk",
+    ),
+    ("cli.directory", "Working copy directory"),
+    (
+        "cli.export",
+        "Export the status in the given format and exit without opening the TUI",
+    ),
+    (
+        "cli.output",
+        "Output file for the export (stdout by default)",
+    ),
+    ("main.write_failed", "Could not write {}"),
+    ("main.directory_failed", "Could not open the directory {}"),
+    ("main.copy_failed", "Could not copy the path: {}"),
+    ("title.project_info", " Project info "),
+    ("title.status", " Status "),
+    ("title.status_filtered", " Status /{} ({}/{}) "),
+    ("title.selected", " Selected "),
+    ("title.commit", " Commit "),
+    ("title.config_error", " Configuration error "),
+    ("title.confirm_revert", " Confirm Revert "),
+    ("title.commit_error", " Commit Error "),
+    ("title.export", " Export "),
+    ("title.export_error", " Export Error "),
+    ("title.committed", " Committed r{} "),
+    ("title.clipboard_error", " Clipboard error "),
+    ("title.watch_error", " Auto-refresh off "),
+    ("title.status_error", " Status error "),
+    ("title.remote_error", " Repository unreachable "),
+    (
+        "msg.remote_off",
+        "Could not ask the repository for changes, showing local changes only.\n{}",
+    ),
+    ("watch.failed", "Could not watch the working copy: {}"),
+    ("job.status", "Refreshing status"),
+    ("job.log", "Reading the log"),
+    ("job.lock", "Reading the lock"),
+    ("job.preview", "Reading the file"),
+    ("job.compare", "Comparing"),
+    (
+        "msg.revision_copied",
+        "Revision {} copied to the clipboard.",
+    ),
+    ("summary.revision", "Committed revision {}."),
+    ("summary.no_revision", "Nothing was committed."),
+    ("summary.sent", "Sent ({}):"),
+    ("summary.added", "Added ({}):"),
+    ("summary.deleted", "Deleted ({}):"),
+    ("summary.repository_output", "Repository:"),
+    ("summary.hook_output", "post_commit hook:"),
+    ("title.help", " Shortcuts: {} "),
+    ("msg.config_defaults", "Default values will be used.\n\n{}"),
+    (
+        "msg.confirm_revert",
+        "Are you sure you want to revert the changes?",
+    ),
+    ("msg.exported", "Status copied to the clipboard as JSON."),
+    ("msg.invalid_index", "(Error: invalid index {})"),
+    ("modal.yes", "Yes ({})"),
+    ("modal.no", "No ({})"),
+    ("modal.commit_anyway", "Commit anyway ({})"),
+    ("modal.cancel", "Cancel ({})"),
+    ("config.read_failed", "Could not read {}: {}"),
+    ("config.parse_failed", "Error in {}: {}"),
+    (
+        "checks.unknown_level",
+        "unknown level '{}', use block, warn or off",
+    ),
+    ("checks.unknown_check", "[checks.level] unknown check '{}'"),
+    (
+        "hooks.unknown",
+        "[hooks] unknown hook '{}', use pre_ or post_ with commit, update, revert or add",
+    ),
+    ("hooks.spawn_failed", "Could not run the {} hook '{}': {}"),
+    (
+        "hooks.stdin_failed",
+        "Could not send the file list to the {} hook '{}': {}",
+    ),
+    ("hooks.failed", "The {} hook '{}' failed ({}):\n{}"),
+    (
+        "commit.invalid_rule",
+        "[commit.rules] invalid pattern '{}': {}",
+    ),
+    ("keys.unknown_modifier", "Unknown modifier '{}' in '{}'"),
+    ("keys.unknown_key", "Unknown key '{}'"),
+    ("keys.unknown_mode", "Unknown mode [keys.{}]"),
+    ("keys.unknown_action", "Unknown action '{}' in [keys.{}]"),
+    ("keys.error_in", "{} in [keys.{}]"),
+    (
+        "keys.conflict",
+        "Key '{}' is bound to both '{}' and '{}' in [keys.{}]",
+    ),
+    ("theme.unknown", "Unknown theme '{}'"),
+    ("theme.unknown_key", "Unknown key [theme.{}] {}"),
+    ("theme.missing_bg", "missing background color in '{}'"),
+    ("theme.invalid_color", "invalid color '{}'"),
+    ("svn.exec_failed", "Failed to run the SVN command: {}"),
+    ("svn.status_failed", "Error getting the SVN status: {}"),
+    ("svn.empty_message", "The commit message cannot be empty."),
+    (
+        "svn.no_selection",
+        "No files have been selected for the commit.",
+    ),
+    ("svn.commit_failed", "Commit error: {}"),
+    (
+        "svn.ignore_no_rule",
+        "There is no pattern of that kind for this entry.",
+    ),
+    (
+        "svn.ignore_failed",
+        "Could not update the ignore property: {}",
+    ),
+    ("svn.proplist_failed", "Could not read the properties: {}"),
+    ("svn.propset_failed", "Could not set '{}': {}"),
+    ("svn.propdel_failed", "Could not delete '{}': {}"),
+    ("svn.diff_failed", "Could not get the diff: {}"),
+    ("svn.info_failed", "Could not read the lock: {}"),
+    (
+        "svn.conflict_info_failed",
+        "Could not read the conflict files: {}",
+    ),
+    ("svn.cat_failed", "Could not get the pristine copy: {}"),
+    ("svn.lock_failed", "Could not lock: {}"),
+    ("svn.unlock_failed", "Could not unlock: {}"),
+    ("svn.update_failed", "Could not update: {}"),
+    ("svn.log_failed", "Could not read the log: {}"),
+    ("svn.summarize_failed", "Could not compare: {}"),
+    ("svn.url_failed", "Could not read the URL of '{}': {}"),
+    ("svn.no_url", "svn info gave no URL"),
+    (
+        "compare.invalid",
+        "'{}' is not a comparison. Use A:B, A:B PATH or two URLs.",
+    ),
+    (
+        "svn.no_author",
+        "No svn user known: set author in [commit] or $USER.",
+    ),
+    (
+        "editor.write_failed",
+        "Could not create the temporary file: {}",
+    ),
+    ("editor.read_failed", "Could not read the edited file: {}"),
+    ("editor.exit_status", "'{}' exited with {}"),
+    (
+        "tools.not_configured",
+        "No {} tool configured. Set it in the [tools] section of the configuration, e.g. diff = \"meld %base %mine\".",
+    ),
+    (
+        "tools.temp_failed",
+        "Could not write the temporary file: {}",
+    ),
+    ("tools.launch_failed", "Could not run '{}': {}"),
+    (
+        "tools.merge_status",
+        "'{}' exited with {}; the conflict was left as it was.",
+    ),
+    (
+        "tools.no_conflict_files",
+        "'{}' has no conflict files; tree conflicts are resolved with svn resolve.",
+    ),
+    (
+        "editor.launch_failed",
+        "Could not start the editor '{}': {}",
+    ),
+    ("mode.normal", "Normal"),
+    ("mode.selections", "Selection"),
+    ("mode.commit", "Commit"),
+    ("mode.confirm", "Confirm"),
+    ("mode.modal", "Message"),
+    ("mode.help", "Help"),
+    ("mode.filter", "Filter"),
+    ("mode.ignore", "Ignore"),
+    ("title.ignore", " Ignore "),
+    ("title.ignored", " Ignored "),
+    ("title.ignore_error", " Ignore error "),
+    ("msg.ignored", "Added '{}' to {} of '{}'."),
+    ("ignore.file", "the file name: {}"),
+    ("ignore.extension", "the extension: {}"),
+    ("ignore.directory", "the directory: {}"),
+    ("ignore.into", "into {} of '{}'"),
+    ("ignore.global", "use svn:global-ignores ({})"),
+    ("ignore.on", "on"),
+    ("ignore.off", "off"),
+    ("ignore.unavailable", "(not available)"),
+    ("mode.properties", "Properties"),
+    ("mode.diff", "Diff"),
+    ("title.properties", " Properties of '{}' "),
+    ("title.diff", " Diff of '{}' "),
+    ("title.property_error", " Property error "),
+    ("title.add_property", " New property "),
+    (
+        "msg.add_property",
+        "Name of the property (svn:eol-style, svn:executable, svn:mime-type, svn:externals, svn:keywords...):",
+    ),
+    ("title.confirm_delete_property", " Delete property "),
+    ("msg.confirm_delete_property", "Delete the property '{}'?"),
+    ("msg.no_properties", "No properties."),
+    ("msg.no_diff", "No local changes."),
+    ("title.diff_error", " Diff error "),
+    ("title.preview", " Preview of '{}' "),
+    ("title.preview_error", " Preview error "),
+    ("title.tool_error", " External tool error "),
+    ("msg.empty_file", "The file is empty."),
+    ("preview.binary", "Binary file, {} bytes: no preview."),
+    ("preview.truncated", " Only the first {} KiB are shown "),
+    ("preview.read_failed", "Could not read '{}': {}"),
+    ("preview.directory", "'{}' is a directory."),
+    ("title.lock", " Lock "),
+    ("msg.lock", "Lock message for {} file(s):"),
+    ("title.locked", " Locked "),
+    ("msg.locked", "Locked {} file(s)."),
+    ("title.unlocked", " Unlocked "),
+    ("msg.unlocked", "Unlocked {} file(s)."),
+    ("title.lock_error", " Lock error "),
+    ("title.confirm_force_lock", " Steal lock "),
+    (
+        "msg.confirm_force_lock",
+        "Steal the locks of {} file(s) from their current owners?",
+    ),
+    ("title.confirm_force_unlock", " Break lock "),
+    (
+        "msg.confirm_force_unlock",
+        "Break the locks of {} file(s), including those held by other users?",
+    ),
+    ("info.lock", "Locked by {} on {}: {}"),
+    ("info.lock_loading", "Locked, reading the details…"),
+    ("info.remote", "[svn status -u]"),
+    ("title.confirm_update", " Out of date "),
+    (
+        "msg.confirm_update",
+        "These files changed in the repository and the commit would fail:\n{}\nUpdate them first?",
+    ),
+    ("title.updated", " Updated "),
+    ("msg.updated", "Updated {} file(s)."),
+    (
+        "msg.updated_commit_again",
+        "Updated {} file(s). Review the changes and commit again.",
+    ),
+    ("title.update_error", " Update error "),
+    ("title.checks_failed", " Commit blocked "),
+    ("title.checks_warn", " Pre-commit warnings "),
+    ("title.hook_failed", " Hook failed "),
+    ("title.hook_warning", " Hook warning "),
+    ("title.log_picker", " My recent commits ({}) "),
+    ("title.compare", " Compare "),
+    (
+        "msg.compare",
+        "Revisions of '{}' (100:HEAD, or a single one to compare with HEAD), a range and a path (100:200 src), or two URLs (^/tags/1.0 ^/trunk):",
+    ),
+    ("title.compare_result", " {} ({} paths) "),
+    ("title.compare_diff", " {} ({}) "),
+    ("title.compare_error", " Compare error "),
+    ("title.log_error", " Log error "),
+    ("title.bugtraq_error", " Issue tracker properties "),
+    ("title.no_issue", " No issue "),
+    (
+        "msg.no_issue",
+        "The commit does not name any issue ({}).\nCommit anyway?",
+    ),
+    ("bugtraq.label", "Issue"),
+    ("bugtraq.invalid_regex", "Invalid bugtraq:logregex '{}': {}"),
+    ("msg.no_log", "No commits of yours found."),
+    ("msg.no_changes", "Both sides are the same."),
+    (
+        "history.write_failed",
+        "Could not save the message history to '{}': {}",
+    ),
+    ("mode.log_picker", "Recent commits"),
+    ("mode.compare", "Compare"),
+    ("mode.commit_summary", "Commit result"),
+    ("title.invalid_message", " Invalid commit message "),
+    ("title.editor_error", " Editor error "),
+    ("check.blocker", "block"),
+    ("check.warning", "warn"),
+    ("check.more", "... and {} more"),
+    ("check.conflict_markers", "conflict markers at line {}"),
+    ("check.conflicted", "still in conflict, resolve it first"),
+    ("check.large_file", "{} KB, above the {} KB limit"),
+    ("check.secret", "line {} looks like a secret ({})"),
+    ("check.private_key", "private key"),
+    ("check.aws_key_id", "AWS access key"),
+    ("check.aws_secret", "AWS secret key"),
+    (
+        "check.trailing_whitespace",
+        "trailing whitespace at line {} ({} lines)",
+    ),
+    ("check.tabs", "tab indentation at line {} ({} lines)"),
+    (
+        "check.unversioned",
+        "is not versioned, the commit would fail",
+    ),
+    ("mode.visual", "Visual"),
+    ("mode.prompt", "Input"),
+    ("title.select_matching", " Select matching "),
+    (
+        "msg.select_matching",
+        "State letter (M, A, ?, ...) or glob pattern:",
+    ),
+    ("msg.invalid_pattern", "Invalid pattern: {}"),
+    ("title.invalid_pattern", " Invalid pattern "),
+    ("action.quit", "Quit the application"),
+    ("action.move_up", "Move the cursor up"),
+    ("action.move_down", "Move the cursor down"),
+    ("action.toggle_selection", "Toggle a file for commit"),
+    ("action.copy_path", "Copy the file path"),
+    ("action.refresh", "Refresh the svn status"),
+    ("action.add", "Add file to the repository"),
+    ("action.revert", "Revert changes"),
+    ("action.export", "Copy the status as JSON"),
+    ("action.commit_mode", "Enter commit mode"),
+    ("action.selections_mode", "Enter selection mode"),
+    ("action.normal_mode", "Back to normal mode"),
+    ("action.submit", "Commit"),
+    ("action.delete_char", "Delete a character"),
+    ("action.confirm", "Confirm"),
+    ("action.cancel", "Cancel"),
+    ("action.close", "Close"),
+    ("action.help", "Show help"),
+    ("action.filter", "Filter the status list"),
+    ("action.toggle_tree", "Toggle the directory tree view"),
+    ("action.toggle_collapse", "Collapse or expand a directory"),
+    ("action.visual_mode", "Select a range of files"),
+    ("action.select_all", "Select every file shown"),
+    (
+        "action.invert_selection",
+        "Invert the selection of the files shown",
+    ),
+    ("action.clear_selection", "Clear the selection"),
+    (
+        "action.select_matching",
+        "Select by state (M, ?) or glob (*.java)",
+    ),
+    ("action.ignore", "Add the file to svn:ignore"),
+    (
+        "action.toggle_unversioned",
+        "Show or hide unversioned files",
+    ),
+    ("action.toggle_ignored", "Show or hide ignored files"),
+    ("action.ignore_file", "Ignore the file name"),
+    ("action.ignore_extension", "Ignore the extension"),
+    ("action.ignore_directory", "Ignore the directory"),
+    (
+        "action.toggle_global",
+        "Switch between svn:ignore and svn:global-ignores",
+    ),
+    ("action.properties", "Show the properties of the file"),
+    ("action.diff", "Show the diff of the file"),
+    ("action.preview", "Show the contents of the file"),
+    (
+        "action.toggle_side_by_side",
+        "Switch the diff between unified and side by side",
+    ),
+    (
+        "action.ignore_whitespace",
+        "Ignore whitespace changes in the diff (-x -w)",
+    ),
+    (
+        "action.ignore_eol_style",
+        "Ignore line ending changes in the diff (--ignore-eol-style)",
+    ),
+    (
+        "action.external_tool",
+        "Open the file in the external diff tool, or the merge tool if conflicted",
+    ),
+    (
+        "action.compare",
+        "Compare two revisions of the file or two URLs",
+    ),
+    ("action.add_property", "Add a property"),
+    ("action.edit_property", "Edit the value in the editor"),
+    ("action.delete_property", "Delete the property"),
+    ("action.page_up", "Scroll a page up"),
+    ("action.page_down", "Scroll a page down"),
+    ("action.lock", "Lock the selected files"),
+    ("action.unlock", "Unlock the selected files"),
+    ("action.force_lock", "Steal the lock of the selected files"),
+    (
+        "action.force_unlock",
+        "Break the lock of the selected files",
+    ),
+    (
+        "action.toggle_remote",
+        "Check the repository (svn status -u)",
+    ),
+    ("action.update", "Update the selected files (svn update)"),
+    ("action.newline", "Insert a new line"),
+    ("action.next_template", "Use the next message template"),
+    (
+        "action.edit_message",
+        "Edit the message in the external editor",
+    ),
+    (
+        "action.log_picker",
+        "Pick the message of one of my recent commits",
+    ),
+    (
+        "action.switch_field",
+        "Switch between the message and the issue field",
+    ),
+    ("action.copy_revision", "Copy the committed revision"),
+    ("hint.quit", "quit"),
+    ("hint.move_up", "up"),
+    ("hint.move_down", "down"),
+    ("hint.toggle_selection", "select"),
+    ("hint.copy_path", "copy path"),
+    ("hint.refresh", "refresh"),
+    ("hint.add", "add"),
+    ("hint.revert", "revert"),
+    ("hint.export", "export"),
+    ("hint.commit_mode", "commit"),
+    ("hint.selections_mode", "selection"),
+    ("hint.normal_mode", "back"),
+    ("hint.submit", "send"),
+    ("hint.delete_char", "delete"),
+    ("hint.confirm", "confirm"),
+    ("hint.cancel", "cancel"),
+    ("hint.close", "close"),
+    ("hint.help", "help"),
+    ("hint.filter", "filter"),
+    ("hint.toggle_tree", "tree"),
+    ("hint.toggle_collapse", "fold"),
+    ("hint.visual_mode", "range"),
+    ("hint.select_all", "all"),
+    ("hint.invert_selection", "invert"),
+    ("hint.clear_selection", "clear"),
+    ("hint.select_matching", "match"),
+    ("hint.ignore", "ignore"),
+    ("hint.toggle_unversioned", "unversioned"),
+    ("hint.toggle_ignored", "ignored"),
+    ("hint.ignore_file", "file"),
+    ("hint.ignore_extension", "extension"),
+    ("hint.ignore_directory", "directory"),
+    ("hint.toggle_global", "global"),
+    ("hint.properties", "props"),
+    ("hint.diff", "diff"),
+    ("hint.preview", "preview"),
+    ("hint.toggle_side_by_side", "side by side"),
+    ("hint.ignore_whitespace", "whitespace"),
+    ("hint.ignore_eol_style", "eol"),
+    ("hint.external_tool", "external diff"),
+    ("hint.compare", "compare"),
+    ("hint.add_property", "add"),
+    ("hint.edit_property", "edit"),
+    ("hint.delete_property", "delete"),
+    ("hint.page_up", "page up"),
+    ("hint.page_down", "page down"),
+    ("hint.lock", "lock"),
+    ("hint.unlock", "unlock"),
+    ("hint.force_lock", "steal lock"),
+    ("hint.force_unlock", "break lock"),
+    ("hint.toggle_remote", "remote"),
+    ("hint.update", "update"),
+    ("hint.newline", "new line"),
+    ("hint.next_template", "template"),
+    ("hint.edit_message", "editor"),
+    ("hint.log_picker", "my commits"),
+    ("hint.switch_field", "issue"),
+    ("hint.copy_revision", "copy revision"),
+];
+
+static ES: &[(&str, &str)] = &[
+    ("cli.about", "Una TUI para proyectos con Svn"),
+    (
+        "cli.long_about",
+        "
+Rustisvn
+
+Una app minimalista y simple para usar en projectos svn.
+
+Funcionalidades disponibles como:
+- Seleccionar archivo
+- Hacer commits
+- Copiar el path del archivo
+- Exportar el estado a JSON/NDJSON
+
+Lo necesario para un flujo simple y sencillo de trabajo en la rama trunck",
+    ),
+    ("cli.directory", "Directorio de la copia de trabajo"),
+    (
+        "cli.export",
+        "Exporta el estado en el formato indicado y termina sin abrir la TUI",
+    ),
+    (
+        "cli.output",
+        "Archivo de salida para la exportación (por defecto stdout)",
+    ),
+    ("main.write_failed", "No se pudo escribir {}"),
+    ("main.directory_failed", "No se pudo abrir el directorio {}"),
+    ("main.copy_failed", "No se pudo copiar la ruta: {}"),
+    ("title.project_info", " Proyecto "),
+    ("title.status", " Estado "),
+    ("title.status_filtered", " Estado /{} ({}/{}) "),
+    ("title.selected", " Seleccionados "),
+    ("title.commit", " Commit "),
+    ("title.config_error", " Error de configuración "),
+    ("title.confirm_revert", " Confirmar Revertir "),
+    ("title.commit_error", " Error de Commit "),
+    ("title.export", " Exportar "),
+    ("title.export_error", " Error al exportar "),
+    ("title.committed", " Commit r{} realizado "),
+    ("title.clipboard_error", " Error del portapapeles "),
+    ("title.watch_error", " Refresco automático desactivado "),
+    ("title.status_error", " Error de estado "),
+    ("title.remote_error", " Repositorio inaccesible "),
+    (
+        "msg.remote_off",
+        "No se pudo consultar el repositorio, solo se muestran los cambios locales.\n{}",
+    ),
+    ("watch.failed", "No se pudo vigilar la copia de trabajo: {}"),
+    ("job.status", "Actualizando estado"),
+    ("job.log", "Leyendo el log"),
+    ("job.lock", "Leyendo el bloqueo"),
+    ("job.preview", "Leyendo el archivo"),
+    ("job.compare", "Comparando"),
+    (
+        "msg.revision_copied",
+        "Revisión {} copiada al portapapeles.",
+    ),
+    ("summary.revision", "Revisión {} creada."),
+    ("summary.no_revision", "No se envió nada."),
+    ("summary.sent", "Enviados ({}):"),
+    ("summary.added", "Añadidos ({}):"),
+    ("summary.deleted", "Eliminados ({}):"),
+    ("summary.repository_output", "Repositorio:"),
+    ("summary.hook_output", "Hook post_commit:"),
+    ("title.help", " Atajos: {} "),
+    (
+        "msg.config_defaults",
+        "Se usarán los valores por defecto.\n\n{}",
+    ),
+    (
+        "msg.confirm_revert",
+        "¿Estás seguro de que quieres revertir los cambios?",
+    ),
+    (
+        "msg.exported",
+        "Estado copiado al portapapeles en formato JSON.",
+    ),
+    ("msg.invalid_index", "(Error: Índice {} inválido)"),
+    ("modal.yes", "Sí ({})"),
+    ("modal.no", "No ({})"),
+    ("modal.commit_anyway", "Hacer commit igualmente ({})"),
+    ("modal.cancel", "Cancelar ({})"),
+    ("config.read_failed", "No se pudo leer {}: {}"),
+    ("config.parse_failed", "Error en {}: {}"),
+    (
+        "checks.unknown_level",
+        "nivel '{}' desconocido, usa block, warn u off",
+    ),
+    (
+        "checks.unknown_check",
+        "[checks.level] comprobación '{}' desconocida",
+    ),
+    (
+        "hooks.unknown",
+        "[hooks] hook '{}' desconocido, usa pre_ o post_ con commit, update, revert o add",
+    ),
+    (
+        "hooks.spawn_failed",
+        "No se pudo ejecutar el hook {} '{}': {}",
+    ),
+    (
+        "hooks.stdin_failed",
+        "No se pudo enviar la lista de archivos al hook {} '{}': {}",
+    ),
+    ("hooks.failed", "El hook {} '{}' falló ({}):\n{}"),
+    (
+        "commit.invalid_rule",
+        "[commit.rules] patrón '{}' inválido: {}",
+    ),
+    (
+        "keys.unknown_modifier",
+        "Modificador desconocido '{}' en '{}'",
+    ),
+    ("keys.unknown_key", "Tecla desconocida '{}'"),
+    ("keys.unknown_mode", "Modo desconocido [keys.{}]"),
+    (
+        "keys.unknown_action",
+        "Acción desconocida '{}' en [keys.{}]",
+    ),
+    ("keys.error_in", "{} en [keys.{}]"),
+    (
+        "keys.conflict",
+        "La tecla '{}' está asignada a '{}' y '{}' en [keys.{}]",
+    ),
+    ("theme.unknown", "Tema desconocido '{}'"),
+    ("theme.unknown_key", "Clave desconocida [theme.{}] {}"),
+    ("theme.missing_bg", "falta el color de fondo en '{}'"),
+    ("theme.invalid_color", "color inválido '{}'"),
+    ("svn.exec_failed", "Fallo al ejecutar el comando SVN: {}"),
+    ("svn.status_failed", "Error al obtener el estado de SVN: {}"),
+    (
+        "svn.empty_message",
+        "El mensaje de commit no puede estar vacío.",
+    ),
+    (
+        "svn.no_selection",
+        "No se han seleccionado archivos para el commit.",
+    ),
+    ("svn.commit_failed", "Error en el commit: {}"),
+    (
+        "svn.ignore_no_rule",
+        "Esta entrada no tiene un patrón de ese tipo.",
+    ),
+    (
+        "svn.ignore_failed",
+        "No se pudo actualizar la propiedad de ignorados: {}",
+    ),
+    (
+        "svn.proplist_failed",
+        "No se pudieron leer las propiedades: {}",
+    ),
+    ("svn.propset_failed", "No se pudo asignar '{}': {}"),
+    ("svn.propdel_failed", "No se pudo borrar '{}': {}"),
+    ("svn.diff_failed", "No se pudo obtener el diff: {}"),
+    ("svn.info_failed", "No se pudo leer el bloqueo: {}"),
+    (
+        "svn.conflict_info_failed",
+        "No se pudieron leer los archivos del conflicto: {}",
+    ),
+    ("svn.cat_failed", "No se pudo obtener la copia original: {}"),
+    ("svn.lock_failed", "No se pudo bloquear: {}"),
+    ("svn.unlock_failed", "No se pudo desbloquear: {}"),
+    ("svn.update_failed", "No se pudo actualizar: {}"),
+    ("svn.log_failed", "No se pudo leer el log: {}"),
+    ("svn.summarize_failed", "No se pudo comparar: {}"),
+    ("svn.url_failed", "No se pudo leer la URL de '{}': {}"),
+    ("svn.no_url", "svn info no devolvió ninguna URL"),
+    (
+        "compare.invalid",
+        "'{}' no es una comparación. Usa A:B, A:B RUTA o dos URLs.",
+    ),
+    (
+        "svn.no_author",
+        "Usuario de svn desconocido: define author en [commit] o $USER.",
+    ),
+    (
+        "editor.write_failed",
+        "No se pudo crear el archivo temporal: {}",
+    ),
+    (
+        "editor.read_failed",
+        "No se pudo leer el archivo editado: {}",
+    ),
+    ("editor.exit_status", "'{}' terminó con {}"),
+    (
+        "tools.not_configured",
+        "No hay herramienta de {} configurada. Se define en la sección [tools] de la configuración, p. ej. diff = \"meld %base %mine\".",
+    ),
+    (
+        "tools.temp_failed",
+        "No se pudo escribir el archivo temporal: {}",
+    ),
+    ("tools.launch_failed", "No se pudo ejecutar '{}': {}"),
+    (
+        "tools.merge_status",
+        "'{}' terminó con {}; el conflicto quedó como estaba.",
+    ),
+    (
+        "tools.no_conflict_files",
+        "'{}' no tiene archivos de conflicto; los conflictos de árbol se resuelven con svn resolve.",
+    ),
+    (
+        "editor.launch_failed",
+        "No se pudo abrir el editor '{}': {}",
+    ),
+    ("mode.normal", "Normal"),
+    ("mode.selections", "Selección"),
+    ("mode.commit", "Commit"),
+    ("mode.confirm", "Confirmar"),
+    ("mode.modal", "Mensaje"),
+    ("mode.help", "Ayuda"),
+    ("mode.filter", "Filtro"),
+    ("mode.ignore", "Ignorar"),
+    ("title.ignore", " Ignorar "),
+    ("title.ignored", " Ignorado "),
+    ("title.ignore_error", " Error al ignorar "),
+    ("msg.ignored", "Se añadió '{}' a {} de '{}'."),
+    ("ignore.file", "el nombre del archivo: {}"),
+    ("ignore.extension", "la extensión: {}"),
+    ("ignore.directory", "el directorio: {}"),
+    ("ignore.into", "en {} de '{}'"),
+    ("ignore.global", "usar svn:global-ignores ({})"),
+    ("ignore.on", "sí"),
+    ("ignore.off", "no"),
+    ("ignore.unavailable", "(no disponible)"),
+    ("mode.properties", "Propiedades"),
+    ("mode.diff", "Diff"),
+    ("title.properties", " Propiedades de '{}' "),
+    ("title.diff", " Diff de '{}' "),
+    ("title.property_error", " Error de propiedad "),
+    ("title.add_property", " Nueva propiedad "),
+    (
+        "msg.add_property",
+        "Nombre de la propiedad (svn:eol-style, svn:executable, svn:mime-type, svn:externals, svn:keywords...):",
+    ),
+    ("title.confirm_delete_property", " Borrar propiedad "),
+    ("msg.confirm_delete_property", "¿Borrar la propiedad '{}'?"),
+    ("msg.no_properties", "Sin propiedades."),
+    ("msg.no_diff", "Sin cambios locales."),
+    ("title.diff_error", " Error de diff "),
+    ("title.preview", " Vista previa de '{}' "),
+    ("title.preview_error", " Error de vista previa "),
+    ("title.tool_error", " Error de herramienta externa "),
+    ("msg.empty_file", "El archivo está vacío."),
+    (
+        "preview.binary",
+        "Archivo binario, {} bytes: sin vista previa.",
+    ),
+    (
+        "preview.truncated",
+        " Solo se muestran los primeros {} KiB ",
+    ),
+    ("preview.read_failed", "No se pudo leer '{}': {}"),
+    ("preview.directory", "'{}' es un directorio."),
+    ("title.lock", " Bloquear "),
+    ("msg.lock", "Mensaje del bloqueo para {} archivo(s):"),
+    ("title.locked", " Bloqueado "),
+    ("msg.locked", "Se bloquearon {} archivo(s)."),
+    ("title.unlocked", " Desbloqueado "),
+    ("msg.unlocked", "Se desbloquearon {} archivo(s)."),
+    ("title.lock_error", " Error de bloqueo "),
+    ("title.confirm_force_lock", " Robar bloqueo "),
+    (
+        "msg.confirm_force_lock",
+        "¿Quitar los bloqueos de {} archivo(s) a sus dueños actuales?",
+    ),
+    ("title.confirm_force_unlock", " Romper bloqueo "),
+    (
+        "msg.confirm_force_unlock",
+        "¿Romper los bloqueos de {} archivo(s), incluso los de otros usuarios?",
+    ),
+    ("info.lock", "Bloqueado por {} el {}: {}"),
+    ("info.lock_loading", "Bloqueado, leyendo los detalles…"),
+    ("info.remote", "[svn status -u]"),
+    ("title.confirm_update", " Desactualizado "),
+    (
+        "msg.confirm_update",
+        "Estos archivos cambiaron en el repositorio y el commit fallaría:\n{}\n¿Actualizarlos primero?",
+    ),
+    ("title.updated", " Actualizado "),
+    ("msg.updated", "Se actualizaron {} archivo(s)."),
+    (
+        "msg.updated_commit_again",
+        "Se actualizaron {} archivo(s). Revisa los cambios y vuelve a hacer el commit.",
+    ),
+    ("title.update_error", " Error al actualizar "),
+    ("title.checks_failed", " Commit bloqueado "),
+    ("title.checks_warn", " Avisos antes del commit "),
+    ("title.hook_failed", " Falló el hook "),
+    ("title.hook_warning", " Aviso del hook "),
+    ("title.log_picker", " Mis commits recientes ({}) "),
+    ("title.compare", " Comparar "),
+    (
+        "msg.compare",
+        "Revisiones de '{}' (100:HEAD, o una sola para comparar con HEAD), un rango y una ruta (100:200 src), o dos URLs (^/tags/1.0 ^/trunk):",
+    ),
+    ("title.compare_result", " {} ({} rutas) "),
+    ("title.compare_diff", " {} ({}) "),
+    ("title.compare_error", " Error al comparar "),
+    ("title.log_error", " Error del log "),
+    (
+        "title.bugtraq_error",
+        " Propiedades del gestor de incidencias ",
+    ),
+    ("title.no_issue", " Sin incidencia "),
+    (
+        "msg.no_issue",
+        "El commit no indica ninguna incidencia ({}).\n¿Hacer el commit igualmente?",
+    ),
+    ("bugtraq.label", "Incidencia"),
+    (
+        "bugtraq.invalid_regex",
+        "bugtraq:logregex '{}' no es válida: {}",
+    ),
+    ("msg.no_log", "No se encontraron commits tuyos."),
+    ("msg.no_changes", "Los dos lados son iguales."),
+    (
+        "history.write_failed",
+        "No se pudo guardar el historial de mensajes en '{}': {}",
+    ),
+    ("mode.log_picker", "Commits recientes"),
+    ("mode.compare", "Comparación"),
+    ("mode.commit_summary", "Resultado del commit"),
+    ("title.invalid_message", " Mensaje de commit inválido "),
+    ("title.editor_error", " Error del editor "),
+    ("check.blocker", "bloquea"),
+    ("check.warning", "aviso"),
+    ("check.more", "... y {} más"),
+    (
+        "check.conflict_markers",
+        "marcas de conflicto en la línea {}",
+    ),
+    ("check.conflicted", "sigue en conflicto, resuélvelo primero"),
+    ("check.large_file", "{} KB, supera el límite de {} KB"),
+    ("check.secret", "la línea {} parece un secreto ({})"),
+    ("check.private_key", "clave privada"),
+    ("check.aws_key_id", "clave de acceso de AWS"),
+    ("check.aws_secret", "clave secreta de AWS"),
+    (
+        "check.trailing_whitespace",
+        "espacios al final en la línea {} ({} líneas)",
+    ),
+    (
+        "check.tabs",
+        "sangría con tabuladores en la línea {} ({} líneas)",
+    ),
+    (
+        "check.unversioned",
+        "no está versionado, el commit fallaría",
+    ),
+    ("mode.visual", "Visual"),
+    ("mode.prompt", "Entrada"),
+    ("title.select_matching", " Seleccionar coincidencias "),
+    (
+        "msg.select_matching",
+        "Letra de estado (M, A, ?, ...) o patrón glob:",
+    ),
+    ("msg.invalid_pattern", "Patrón inválido: {}"),
+    ("title.invalid_pattern", " Patrón inválido "),
+    ("action.quit", "Salir de la aplicación"),
+    ("action.move_up", "Subir el cursor"),
+    ("action.move_down", "Bajar el cursor"),
+    ("action.toggle_selection", "Alternar un archivo para commit"),
+    ("action.copy_path", "Copiar la ruta del archivo"),
+    ("action.refresh", "Actualizar el estado de svn"),
+    ("action.add", "Añadir archivo al repositorio"),
+    ("action.revert", "Revertir cambios"),
+    ("action.export", "Copiar el estado en JSON"),
+    ("action.commit_mode", "Entrar al modo commit"),
+    ("action.selections_mode", "Entrar al modo selección"),
+    ("action.normal_mode", "Volver al modo normal"),
+    ("action.submit", "Realizar el commit"),
+    ("action.delete_char", "Borrar un carácter"),
+    ("action.confirm", "Confirmar"),
+    ("action.cancel", "Cancelar"),
+    ("action.close", "Cerrar"),
+    ("action.help", "Mostrar la ayuda"),
+    ("action.filter", "Filtrar la lista de estado"),
+    ("action.toggle_tree", "Alternar la vista de árbol"),
+    ("action.toggle_collapse", "Plegar o desplegar un directorio"),
+    ("action.visual_mode", "Seleccionar un rango de archivos"),
+    (
+        "action.select_all",
+        "Seleccionar todos los archivos visibles",
+    ),
+    (
+        "action.invert_selection",
+        "Invertir la selección de los archivos visibles",
+    ),
+    ("action.clear_selection", "Limpiar la selección"),
+    (
+        "action.select_matching",
+        "Seleccionar por estado (M, ?) o patrón (*.java)",
+    ),
+    ("action.ignore", "Añadir el archivo a svn:ignore"),
+    (
+        "action.toggle_unversioned",
+        "Mostrar u ocultar archivos sin versionar",
+    ),
+    (
+        "action.toggle_ignored",
+        "Mostrar u ocultar archivos ignorados",
+    ),
+    ("action.ignore_file", "Ignorar el nombre del archivo"),
+    ("action.ignore_extension", "Ignorar la extensión"),
+    ("action.ignore_directory", "Ignorar el directorio"),
+    (
+        "action.toggle_global",
+        "Alternar entre svn:ignore y svn:global-ignores",
+    ),
+    ("action.properties", "Mostrar las propiedades del archivo"),
+    ("action.diff", "Mostrar el diff del archivo"),
+    ("action.preview", "Mostrar el contenido del archivo"),
+    (
+        "action.toggle_side_by_side",
+        "Alternar el diff entre unificado y lado a lado",
+    ),
+    (
+        "action.ignore_whitespace",
+        "Ignorar cambios de espacios en el diff (-x -w)",
+    ),
+    (
+        "action.ignore_eol_style",
+        "Ignorar cambios de fin de línea en el diff (--ignore-eol-style)",
+    ),
+    (
+        "action.external_tool",
+        "Abrir el archivo en la herramienta de diff externa, o la de merge si tiene conflicto",
+    ),
+    (
+        "action.compare",
+        "Comparar dos revisiones del archivo o dos URLs",
+    ),
+    ("action.add_property", "Añadir una propiedad"),
+    ("action.edit_property", "Editar el valor en el editor"),
+    ("action.delete_property", "Borrar la propiedad"),
+    ("action.page_up", "Subir una página"),
+    ("action.page_down", "Bajar una página"),
+    ("action.lock", "Bloquear los archivos seleccionados"),
+    ("action.unlock", "Desbloquear los archivos seleccionados"),
+    (
+        "action.force_lock",
+        "Robar el bloqueo de los archivos seleccionados",
+    ),
+    (
+        "action.force_unlock",
+        "Romper el bloqueo de los archivos seleccionados",
+    ),
+    (
+        "action.toggle_remote",
+        "Consultar el repositorio (svn status -u)",
+    ),
+    (
+        "action.update",
+        "Actualizar los archivos seleccionados (svn update)",
+    ),
+    ("action.newline", "Insertar un salto de línea"),
+    (
+        "action.next_template",
+        "Usar la siguiente plantilla de mensaje",
+    ),
+    (
+        "action.edit_message",
+        "Editar el mensaje en el editor externo",
+    ),
+    (
+        "action.log_picker",
+        "Elegir el mensaje de uno de mis commits recientes",
+    ),
+    (
+        "action.switch_field",
+        "Cambiar entre el mensaje y el campo de incidencia",
+    ),
+    ("action.copy_revision", "Copiar la revisión del commit"),
+    ("hint.quit", "salir"),
+    ("hint.move_up", "subir"),
+    ("hint.move_down", "bajar"),
+    ("hint.toggle_selection", "seleccionar"),
+    ("hint.copy_path", "copiar ruta"),
+    ("hint.refresh", "actualizar"),
+    ("hint.add", "añadir"),
+    ("hint.revert", "revertir"),
+    ("hint.export", "exportar"),
+    ("hint.commit_mode", "commit"),
+    ("hint.selections_mode", "selección"),
+    ("hint.normal_mode", "volver"),
+    ("hint.submit", "enviar"),
+    ("hint.delete_char", "borrar"),
+    ("hint.confirm", "confirmar"),
+    ("hint.cancel", "cancelar"),
+    ("hint.close", "cerrar"),
+    ("hint.help", "ayuda"),
+    ("hint.filter", "filtrar"),
+    ("hint.toggle_tree", "árbol"),
+    ("hint.toggle_collapse", "plegar"),
+    ("hint.visual_mode", "rango"),
+    ("hint.select_all", "todos"),
+    ("hint.invert_selection", "invertir"),
+    ("hint.clear_selection", "limpiar"),
+    ("hint.select_matching", "patrón"),
+    ("hint.ignore", "ignorar"),
+    ("hint.toggle_unversioned", "sin versionar"),
+    ("hint.toggle_ignored", "ignorados"),
+    ("hint.ignore_file", "archivo"),
+    ("hint.ignore_extension", "extensión"),
+    ("hint.ignore_directory", "directorio"),
+    ("hint.toggle_global", "global"),
+    ("hint.properties", "props"),
+    ("hint.diff", "diff"),
+    ("hint.preview", "vista previa"),
+    ("hint.toggle_side_by_side", "lado a lado"),
+    ("hint.ignore_whitespace", "espacios"),
+    ("hint.ignore_eol_style", "fin de línea"),
+    ("hint.external_tool", "diff externo"),
+    ("hint.compare", "comparar"),
+    ("hint.add_property", "añadir"),
+    ("hint.edit_property", "editar"),
+    ("hint.delete_property", "borrar"),
+    ("hint.page_up", "pág. arriba"),
+    ("hint.page_down", "pág. abajo"),
+    ("hint.lock", "bloquear"),
+    ("hint.unlock", "desbloquear"),
+    ("hint.force_lock", "robar bloqueo"),
+    ("hint.force_unlock", "romper bloqueo"),
+    ("hint.toggle_remote", "remoto"),
+    ("hint.update", "actualizar"),
+    ("hint.newline", "nueva línea"),
+    ("hint.next_template", "plantilla"),
+    ("hint.edit_message", "editor"),
+    ("hint.log_picker", "mis commits"),
+    ("hint.switch_field", "incidencia"),
+    ("hint.copy_revision", "copiar revisión"),
+];
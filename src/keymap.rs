@@ -0,0 +1,952 @@
+use crate::{
+    config::KeysConfig,
+    i18n::{tr, trf},
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{collections::HashMap, fmt};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    MoveUp,
+    MoveDown,
+    ToggleSelection,
+    CopyPath,
+    Refresh,
+    Add,
+    Revert,
+    Export,
+    CommitMode,
+    SelectionsMode,
+    NormalMode,
+    Submit,
+    DeleteChar,
+    Confirm,
+    Cancel,
+    Close,
+    Help,
+    Filter,
+    ToggleTree,
+    ToggleCollapse,
+    VisualMode,
+    SelectAll,
+    InvertSelection,
+    ClearSelection,
+    SelectMatching,
+    Ignore,
+    ToggleUnversioned,
+    ToggleIgnored,
+    IgnoreFile,
+    IgnoreExtension,
+    IgnoreDirectory,
+    ToggleGlobal,
+    Properties,
+    Diff,
+    AddProperty,
+    EditProperty,
+    DeleteProperty,
+    PageUp,
+    PageDown,
+    Lock,
+    Unlock,
+    ForceLock,
+    ForceUnlock,
+    ToggleRemote,
+    Update,
+    Newline,
+    NextTemplate,
+    EditMessage,
+    LogPicker,
+    SwitchField,
+    CopyRevision,
+    Preview,
+    ToggleSideBySide,
+    IgnoreWhitespace,
+    IgnoreEolStyle,
+    ExternalTool,
+    Compare,
+}
+
+impl Action {
+    pub const ALL: [Action; 58] = [
+        Action::Quit,
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::ToggleSelection,
+        Action::CopyPath,
+        Action::Refresh,
+        Action::Add,
+        Action::Revert,
+        Action::Export,
+        Action::CommitMode,
+        Action::SelectionsMode,
+        Action::NormalMode,
+        Action::Submit,
+        Action::DeleteChar,
+        Action::Confirm,
+        Action::Cancel,
+        Action::Close,
+        Action::Help,
+        Action::Filter,
+        Action::ToggleTree,
+        Action::ToggleCollapse,
+        Action::VisualMode,
+        Action::SelectAll,
+        Action::InvertSelection,
+        Action::ClearSelection,
+        Action::SelectMatching,
+        Action::Ignore,
+        Action::ToggleUnversioned,
+        Action::ToggleIgnored,
+        Action::IgnoreFile,
+        Action::IgnoreExtension,
+        Action::IgnoreDirectory,
+        Action::ToggleGlobal,
+        Action::Properties,
+        Action::Diff,
+        Action::AddProperty,
+        Action::EditProperty,
+        Action::DeleteProperty,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Lock,
+        Action::Unlock,
+        Action::ForceLock,
+        Action::ForceUnlock,
+        Action::ToggleRemote,
+        Action::Update,
+        Action::Newline,
+        Action::NextTemplate,
+        Action::EditMessage,
+        Action::LogPicker,
+        Action::SwitchField,
+        Action::CopyRevision,
+        Action::Preview,
+        Action::ToggleSideBySide,
+        Action::IgnoreWhitespace,
+        Action::IgnoreEolStyle,
+        Action::ExternalTool,
+        Action::Compare,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::ToggleSelection => "toggle_selection",
+            Action::CopyPath => "copy_path",
+            Action::Refresh => "refresh",
+            Action::Add => "add",
+            Action::Revert => "revert",
+            Action::Export => "export",
+            Action::CommitMode => "commit_mode",
+            Action::SelectionsMode => "selections_mode",
+            Action::NormalMode => "normal_mode",
+            Action::Submit => "submit",
+            Action::DeleteChar => "delete_char",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+            Action::Close => "close",
+            Action::Help => "help",
+            Action::Filter => "filter",
+            Action::ToggleTree => "toggle_tree",
+            Action::ToggleCollapse => "toggle_collapse",
+            Action::VisualMode => "visual_mode",
+            Action::SelectAll => "select_all",
+            Action::InvertSelection => "invert_selection",
+            Action::ClearSelection => "clear_selection",
+            Action::SelectMatching => "select_matching",
+            Action::Ignore => "ignore",
+            Action::ToggleUnversioned => "toggle_unversioned",
+            Action::ToggleIgnored => "toggle_ignored",
+            Action::IgnoreFile => "ignore_file",
+            Action::IgnoreExtension => "ignore_extension",
+            Action::IgnoreDirectory => "ignore_directory",
+            Action::ToggleGlobal => "toggle_global",
+            Action::Properties => "properties",
+            Action::Diff => "diff",
+            Action::AddProperty => "add_property",
+            Action::EditProperty => "edit_property",
+            Action::DeleteProperty => "delete_property",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Lock => "lock",
+            Action::Unlock => "unlock",
+            Action::ForceLock => "force_lock",
+            Action::ForceUnlock => "force_unlock",
+            Action::ToggleRemote => "toggle_remote",
+            Action::Update => "update",
+            Action::Newline => "newline",
+            Action::NextTemplate => "next_template",
+            Action::EditMessage => "edit_message",
+            Action::LogPicker => "log_picker",
+            Action::SwitchField => "switch_field",
+            Action::CopyRevision => "copy_revision",
+            Action::Preview => "preview",
+            Action::ToggleSideBySide => "toggle_side_by_side",
+            Action::IgnoreWhitespace => "ignore_whitespace",
+            Action::IgnoreEolStyle => "ignore_eol_style",
+            Action::ExternalTool => "external_tool",
+            Action::Compare => "compare",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => tr("action.quit"),
+            Action::MoveUp => tr("action.move_up"),
+            Action::MoveDown => tr("action.move_down"),
+            Action::ToggleSelection => tr("action.toggle_selection"),
+            Action::CopyPath => tr("action.copy_path"),
+            Action::Refresh => tr("action.refresh"),
+            Action::Add => tr("action.add"),
+            Action::Revert => tr("action.revert"),
+            Action::Export => tr("action.export"),
+            Action::CommitMode => tr("action.commit_mode"),
+            Action::SelectionsMode => tr("action.selections_mode"),
+            Action::NormalMode => tr("action.normal_mode"),
+            Action::Submit => tr("action.submit"),
+            Action::DeleteChar => tr("action.delete_char"),
+            Action::Confirm => tr("action.confirm"),
+            Action::Cancel => tr("action.cancel"),
+            Action::Close => tr("action.close"),
+            Action::Help => tr("action.help"),
+            Action::Filter => tr("action.filter"),
+            Action::ToggleTree => tr("action.toggle_tree"),
+            Action::ToggleCollapse => tr("action.toggle_collapse"),
+            Action::VisualMode => tr("action.visual_mode"),
+            Action::SelectAll => tr("action.select_all"),
+            Action::InvertSelection => tr("action.invert_selection"),
+            Action::ClearSelection => tr("action.clear_selection"),
+            Action::SelectMatching => tr("action.select_matching"),
+            Action::Ignore => tr("action.ignore"),
+            Action::ToggleUnversioned => tr("action.toggle_unversioned"),
+            Action::ToggleIgnored => tr("action.toggle_ignored"),
+            Action::IgnoreFile => tr("action.ignore_file"),
+            Action::IgnoreExtension => tr("action.ignore_extension"),
+            Action::IgnoreDirectory => tr("action.ignore_directory"),
+            Action::ToggleGlobal => tr("action.toggle_global"),
+            Action::Properties => tr("action.properties"),
+            Action::Diff => tr("action.diff"),
+            Action::AddProperty => tr("action.add_property"),
+            Action::EditProperty => tr("action.edit_property"),
+            Action::DeleteProperty => tr("action.delete_property"),
+            Action::PageUp => tr("action.page_up"),
+            Action::PageDown => tr("action.page_down"),
+            Action::Lock => tr("action.lock"),
+            Action::Unlock => tr("action.unlock"),
+            Action::ForceLock => tr("action.force_lock"),
+            Action::ForceUnlock => tr("action.force_unlock"),
+            Action::ToggleRemote => tr("action.toggle_remote"),
+            Action::Update => tr("action.update"),
+            Action::Newline => tr("action.newline"),
+            Action::NextTemplate => tr("action.next_template"),
+            Action::EditMessage => tr("action.edit_message"),
+            Action::LogPicker => tr("action.log_picker"),
+            Action::SwitchField => tr("action.switch_field"),
+            Action::CopyRevision => tr("action.copy_revision"),
+            Action::Preview => tr("action.preview"),
+            Action::ToggleSideBySide => tr("action.toggle_side_by_side"),
+            Action::IgnoreWhitespace => tr("action.ignore_whitespace"),
+            Action::IgnoreEolStyle => tr("action.ignore_eol_style"),
+            Action::ExternalTool => tr("action.external_tool"),
+            Action::Compare => tr("action.compare"),
+        }
+    }
+
+    pub fn short_description(&self) -> &'static str {
+        match self {
+            Action::Quit => tr("hint.quit"),
+            Action::MoveUp => tr("hint.move_up"),
+            Action::MoveDown => tr("hint.move_down"),
+            Action::ToggleSelection => tr("hint.toggle_selection"),
+            Action::CopyPath => tr("hint.copy_path"),
+            Action::Refresh => tr("hint.refresh"),
+            Action::Add => tr("hint.add"),
+            Action::Revert => tr("hint.revert"),
+            Action::Export => tr("hint.export"),
+            Action::CommitMode => tr("hint.commit_mode"),
+            Action::SelectionsMode => tr("hint.selections_mode"),
+            Action::NormalMode => tr("hint.normal_mode"),
+            Action::Submit => tr("hint.submit"),
+            Action::DeleteChar => tr("hint.delete_char"),
+            Action::Confirm => tr("hint.confirm"),
+            Action::Cancel => tr("hint.cancel"),
+            Action::Close => tr("hint.close"),
+            Action::Help => tr("hint.help"),
+            Action::Filter => tr("hint.filter"),
+            Action::ToggleTree => tr("hint.toggle_tree"),
+            Action::ToggleCollapse => tr("hint.toggle_collapse"),
+            Action::VisualMode => tr("hint.visual_mode"),
+            Action::SelectAll => tr("hint.select_all"),
+            Action::InvertSelection => tr("hint.invert_selection"),
+            Action::ClearSelection => tr("hint.clear_selection"),
+            Action::SelectMatching => tr("hint.select_matching"),
+            Action::Ignore => tr("hint.ignore"),
+            Action::ToggleUnversioned => tr("hint.toggle_unversioned"),
+            Action::ToggleIgnored => tr("hint.toggle_ignored"),
+            Action::IgnoreFile => tr("hint.ignore_file"),
+            Action::IgnoreExtension => tr("hint.ignore_extension"),
+            Action::IgnoreDirectory => tr("hint.ignore_directory"),
+            Action::ToggleGlobal => tr("hint.toggle_global"),
+            Action::Properties => tr("hint.properties"),
+            Action::Diff => tr("hint.diff"),
+            Action::AddProperty => tr("hint.add_property"),
+            Action::EditProperty => tr("hint.edit_property"),
+            Action::DeleteProperty => tr("hint.delete_property"),
+            Action::PageUp => tr("hint.page_up"),
+            Action::PageDown => tr("hint.page_down"),
+            Action::Lock => tr("hint.lock"),
+            Action::Unlock => tr("hint.unlock"),
+            Action::ForceLock => tr("hint.force_lock"),
+            Action::ForceUnlock => tr("hint.force_unlock"),
+            Action::ToggleRemote => tr("hint.toggle_remote"),
+            Action::Update => tr("hint.update"),
+            Action::Newline => tr("hint.newline"),
+            Action::NextTemplate => tr("hint.next_template"),
+            Action::EditMessage => tr("hint.edit_message"),
+            Action::LogPicker => tr("hint.log_picker"),
+            Action::SwitchField => tr("hint.switch_field"),
+            Action::CopyRevision => tr("hint.copy_revision"),
+            Action::Preview => tr("hint.preview"),
+            Action::ToggleSideBySide => tr("hint.toggle_side_by_side"),
+            Action::IgnoreWhitespace => tr("hint.ignore_whitespace"),
+            Action::IgnoreEolStyle => tr("hint.ignore_eol_style"),
+            Action::ExternalTool => tr("hint.external_tool"),
+            Action::Compare => tr("hint.compare"),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyContext {
+    Normal,
+    Selections,
+    Commit,
+    Confirm,
+    Modal,
+    Help,
+    Filter,
+    Visual,
+    Prompt,
+    Ignore,
+    Properties,
+    Diff,
+    LogPicker,
+    CommitSummary,
+    Compare,
+}
+
+impl KeyContext {
+    pub const ALL: [KeyContext; 15] = [
+        KeyContext::Normal,
+        KeyContext::Selections,
+        KeyContext::Commit,
+        KeyContext::Confirm,
+        KeyContext::Modal,
+        KeyContext::Help,
+        KeyContext::Filter,
+        KeyContext::Visual,
+        KeyContext::Prompt,
+        KeyContext::Ignore,
+        KeyContext::Properties,
+        KeyContext::Diff,
+        KeyContext::LogPicker,
+        KeyContext::CommitSummary,
+        KeyContext::Compare,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyContext::Normal => "normal",
+            KeyContext::Selections => "selections",
+            KeyContext::Commit => "commit",
+            KeyContext::Confirm => "confirm",
+            KeyContext::Modal => "modal",
+            KeyContext::Help => "help",
+            KeyContext::Filter => "filter",
+            KeyContext::Visual => "visual",
+            KeyContext::Prompt => "prompt",
+            KeyContext::Ignore => "ignore",
+            KeyContext::Properties => "properties",
+            KeyContext::Diff => "diff",
+            KeyContext::LogPicker => "log_picker",
+            KeyContext::CommitSummary => "commit_summary",
+            KeyContext::Compare => "compare",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Normal => tr("mode.normal"),
+            KeyContext::Selections => tr("mode.selections"),
+            KeyContext::Commit => tr("mode.commit"),
+            KeyContext::Confirm => tr("mode.confirm"),
+            KeyContext::Modal => tr("mode.modal"),
+            KeyContext::Help => tr("mode.help"),
+            KeyContext::Filter => tr("mode.filter"),
+            KeyContext::Visual => tr("mode.visual"),
+            KeyContext::Prompt => tr("mode.prompt"),
+            KeyContext::Ignore => tr("mode.ignore"),
+            KeyContext::Properties => tr("mode.properties"),
+            KeyContext::Diff => tr("mode.diff"),
+            KeyContext::LogPicker => tr("mode.log_picker"),
+            KeyContext::CommitSummary => tr("mode.commit_summary"),
+            KeyContext::Compare => tr("mode.compare"),
+        }
+    }
+
+    /// Actions shown in the hint bar, most important first
+    pub fn hint_actions(&self) -> &'static [Action] {
+        match self {
+            KeyContext::Normal => &[
+                Action::Help,
+                Action::ToggleSelection,
+                Action::CommitMode,
+                Action::SelectionsMode,
+                Action::Filter,
+                Action::Quit,
+            ],
+            KeyContext::Selections => &[
+                Action::Help,
+                Action::ToggleSelection,
+                Action::CommitMode,
+                Action::NormalMode,
+            ],
+            KeyContext::Commit => &[
+                Action::Help,
+                Action::Submit,
+                Action::Newline,
+                Action::EditMessage,
+                Action::LogPicker,
+                Action::SwitchField,
+                Action::NormalMode,
+            ],
+            KeyContext::Filter => &[Action::Submit, Action::Cancel, Action::MoveDown],
+            KeyContext::Visual => &[Action::ToggleSelection, Action::NormalMode],
+            KeyContext::Prompt => &[Action::Submit, Action::Cancel],
+            KeyContext::Ignore => &[Action::ToggleGlobal, Action::Cancel],
+            KeyContext::Properties => &[
+                Action::Help,
+                Action::AddProperty,
+                Action::EditProperty,
+                Action::DeleteProperty,
+                Action::Diff,
+                Action::Cancel,
+            ],
+            KeyContext::Diff => &[
+                Action::Help,
+                Action::ToggleSideBySide,
+                Action::IgnoreWhitespace,
+                Action::PageDown,
+                Action::Close,
+            ],
+            KeyContext::LogPicker => &[Action::Submit, Action::Cancel],
+            KeyContext::Compare => &[Action::Diff, Action::Close],
+            KeyContext::CommitSummary => &[Action::CopyRevision, Action::Close],
+            KeyContext::Confirm => &[Action::Confirm, Action::Cancel],
+            KeyContext::Modal | KeyContext::Help => &[Action::Close],
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        KeyContext::ALL.into_iter().find(|ctx| ctx.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyBinding {
+    pub modifiers: KeyModifiers,
+    pub code: KeyCode,
+}
+
+impl KeyBinding {
+    pub fn new(modifiers: KeyModifiers, code: KeyCode) -> Self {
+        KeyBinding { modifiers, code }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        // A lone "+" is a valid key, so only split while a modifier prefix remains
+        while let Some((prefix, tail)) = rest.split_once('+') {
+            if tail.is_empty() {
+                break;
+            }
+            match prefix.to_lowercase().as_str() {
+                "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+                "alt" => modifiers |= KeyModifiers::ALT,
+                "shift" => modifiers |= KeyModifiers::SHIFT,
+                _ => {
+                    return Err(trf("keys.unknown_modifier", &[&prefix, &text]));
+                }
+            }
+            rest = tail;
+        }
+        let code = match rest.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "enter" | "return" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "backspace" => KeyCode::Backspace,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            lower => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => return Err(trf("keys.unknown_key", &[&text])),
+                    },
+                }
+            }
+        };
+        Ok(KeyBinding::new(modifiers, code))
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        match (self.code, key.code) {
+            // Shift is already encoded in the character itself
+            (KeyCode::Char(a), KeyCode::Char(b)) => {
+                let wanted = self.modifiers - KeyModifiers::SHIFT;
+                let got = key.modifiers - KeyModifiers::SHIFT;
+                if wanted != got {
+                    return false;
+                }
+                if wanted.contains(KeyModifiers::CONTROL) {
+                    a.eq_ignore_ascii_case(&b)
+                } else {
+                    a == b
+                }
+            }
+            (a, b) => a == b && self.modifiers == key.modifiers,
+        }
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "shift+")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Enter => write!(f, "enter"),
+            KeyCode::Esc => write!(f, "esc"),
+            KeyCode::Backspace => write!(f, "backspace"),
+            KeyCode::Tab => write!(f, "tab"),
+            KeyCode::BackTab => write!(f, "backtab"),
+            KeyCode::Up => write!(f, "up"),
+            KeyCode::Down => write!(f, "down"),
+            KeyCode::Left => write!(f, "left"),
+            KeyCode::Right => write!(f, "right"),
+            KeyCode::Home => write!(f, "home"),
+            KeyCode::End => write!(f, "end"),
+            KeyCode::PageUp => write!(f, "pageup"),
+            KeyCode::PageDown => write!(f, "pagedown"),
+            KeyCode::Delete => write!(f, "delete"),
+            KeyCode::Insert => write!(f, "insert"),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<KeyContext, Vec<(KeyBinding, Action)>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let mut keymap = Keymap {
+            bindings: HashMap::new(),
+        };
+        for ctx in KeyContext::ALL {
+            for (action, keys) in default_bindings(ctx) {
+                for key in keys.iter() {
+                    let binding = KeyBinding::parse(key).expect("default binding");
+                    keymap
+                        .bindings
+                        .entry(ctx)
+                        .or_default()
+                        .push((binding, *action));
+                }
+            }
+        }
+        keymap
+    }
+}
+
+impl Keymap {
+    /// Builds the keymap from the config, overriding default keys per action.
+    /// On any problem the default keymap is kept and the problems are returned.
+    pub fn from_config(config: &KeysConfig) -> (Self, Vec<String>) {
+        let mut keymap = Keymap::default();
+        let mut errors = Vec::new();
+        for (ctx_name, actions) in config {
+            let Some(ctx) = KeyContext::from_name(ctx_name) else {
+                errors.push(trf("keys.unknown_mode", &[ctx_name]));
+                continue;
+            };
+            let bindings = keymap.bindings.entry(ctx).or_default();
+            for (action_name, keys) in actions {
+                let Some(action) = Action::from_name(action_name) else {
+                    errors.push(trf("keys.unknown_action", &[action_name, ctx_name]));
+                    continue;
+                };
+                bindings.retain(|(_, bound)| *bound != action);
+                for key in keys.keys() {
+                    match KeyBinding::parse(key) {
+                        Ok(binding) => bindings.push((binding, action)),
+                        Err(e) => errors.push(trf("keys.error_in", &[&e, ctx_name])),
+                    }
+                }
+            }
+        }
+        errors.extend(keymap.conflicts());
+        if errors.is_empty() {
+            (keymap, errors)
+        } else {
+            (Keymap::default(), errors)
+        }
+    }
+
+    fn conflicts(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for ctx in KeyContext::ALL {
+            let bindings = self.bindings(ctx);
+            for (i, (binding, action)) in bindings.iter().enumerate() {
+                for (other_binding, other_action) in &bindings[i + 1..] {
+                    if binding == other_binding && action != other_action {
+                        errors.push(trf(
+                            "keys.conflict",
+                            &[binding, &action.name(), &other_action.name(), &ctx.name()],
+                        ));
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    pub fn bindings(&self, ctx: KeyContext) -> &[(KeyBinding, Action)] {
+        self.bindings.get(&ctx).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Bindings of a mode grouped by action, in the order they were declared
+    pub fn grouped(&self, ctx: KeyContext) -> Vec<(Action, Vec<KeyBinding>)> {
+        let mut grouped: Vec<(Action, Vec<KeyBinding>)> = Vec::new();
+        for (binding, action) in self.bindings(ctx) {
+            match grouped.iter_mut().find(|(bound, _)| bound == action) {
+                Some((_, keys)) => keys.push(*binding),
+                None => grouped.push((*action, vec![*binding])),
+            }
+        }
+        grouped
+    }
+
+    pub fn keys_for(&self, ctx: KeyContext, action: Action) -> Vec<KeyBinding> {
+        self.bindings(ctx)
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(binding, _)| *binding)
+            .collect()
+    }
+
+    pub fn action(&self, ctx: KeyContext, key: &KeyEvent) -> Option<Action> {
+        self.bindings(ctx)
+            .iter()
+            .find(|(binding, _)| binding.matches(key))
+            .map(|(_, action)| *action)
+    }
+}
+
+fn default_bindings(ctx: KeyContext) -> &'static [(Action, &'static [&'static str])] {
+    match ctx {
+        KeyContext::Normal => &[
+            (Action::Quit, &["esc", "q", "ctrl+c"]),
+            (Action::MoveUp, &["up", "k"]),
+            (Action::MoveDown, &["down", "j"]),
+            (Action::CopyPath, &["y"]),
+            (Action::Refresh, &["u"]),
+            (Action::Add, &["a"]),
+            (Action::Revert, &["r"]),
+            (Action::ToggleSelection, &["space"]),
+            (Action::CommitMode, &["c"]),
+            (Action::SelectionsMode, &["s"]),
+            (Action::Export, &["e"]),
+            (Action::Filter, &["/"]),
+            (Action::ToggleTree, &["t"]),
+            (Action::ToggleCollapse, &["enter"]),
+            (Action::VisualMode, &["v"]),
+            (Action::SelectAll, &["A"]),
+            (Action::InvertSelection, &["i"]),
+            (Action::ClearSelection, &["x"]),
+            (Action::SelectMatching, &["="]),
+            (Action::Ignore, &["I"]),
+            (Action::ToggleUnversioned, &["H"]),
+            (Action::ToggleIgnored, &["N"]),
+            (Action::Properties, &["p"]),
+            (Action::Diff, &["d"]),
+            (Action::Preview, &["o"]),
+            (Action::ExternalTool, &["D"]),
+            (Action::Compare, &["C"]),
+            (Action::Lock, &["l"]),
+            (Action::Unlock, &["L"]),
+            (Action::ForceLock, &["alt+l"]),
+            (Action::ForceUnlock, &["alt+L"]),
+            (Action::ToggleRemote, &["R"]),
+            (Action::Update, &["U"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Selections => &[
+            (Action::NormalMode, &["esc"]),
+            (Action::Quit, &["q", "ctrl+c"]),
+            (Action::CommitMode, &["c"]),
+            (Action::MoveUp, &["up", "k"]),
+            (Action::MoveDown, &["down", "j"]),
+            (Action::ToggleSelection, &["space"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Commit => &[
+            (Action::NormalMode, &["esc"]),
+            (Action::Submit, &["enter"]),
+            (Action::Newline, &["alt+enter", "ctrl+j"]),
+            (Action::DeleteChar, &["backspace"]),
+            (Action::NextTemplate, &["ctrl+t"]),
+            (Action::EditMessage, &["ctrl+e"]),
+            (Action::MoveUp, &["up"]),
+            (Action::MoveDown, &["down"]),
+            (Action::LogPicker, &["ctrl+r"]),
+            (Action::SwitchField, &["tab"]),
+            (Action::Help, &["f1"]),
+        ],
+        KeyContext::LogPicker => &[
+            (Action::Cancel, &["esc"]),
+            (Action::Submit, &["enter"]),
+            (Action::MoveUp, &["up", "k"]),
+            (Action::MoveDown, &["down", "j"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Compare => &[
+            (Action::Close, &["esc", "q"]),
+            (Action::Diff, &["enter", "d"]),
+            (Action::MoveUp, &["up", "k"]),
+            (Action::MoveDown, &["down", "j"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Confirm => &[
+            (Action::Cancel, &["esc", "backspace", "n"]),
+            (Action::Confirm, &["y"]),
+        ],
+        KeyContext::Modal => &[(Action::Close, &["enter", "esc"])],
+        KeyContext::CommitSummary => &[
+            (Action::Close, &["enter", "esc", "q"]),
+            (Action::CopyRevision, &["y"]),
+        ],
+        KeyContext::Help => &[(Action::Close, &["esc", "enter", "q", "?", "f1"])],
+        KeyContext::Filter => &[
+            (Action::Submit, &["enter"]),
+            (Action::Cancel, &["esc"]),
+            (Action::DeleteChar, &["backspace"]),
+            (Action::MoveUp, &["up"]),
+            (Action::MoveDown, &["down"]),
+            (Action::Help, &["f1"]),
+        ],
+        KeyContext::Visual => &[
+            (Action::NormalMode, &["esc"]),
+            (Action::MoveUp, &["up", "k"]),
+            (Action::MoveDown, &["down", "j"]),
+            (Action::ToggleSelection, &["space", "v"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Ignore => &[
+            (Action::IgnoreFile, &["f"]),
+            (Action::IgnoreExtension, &["e"]),
+            (Action::IgnoreDirectory, &["d"]),
+            (Action::ToggleGlobal, &["g"]),
+            (Action::Cancel, &["esc", "q"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Properties => &[
+            (Action::Cancel, &["esc", "q"]),
+            (Action::MoveUp, &["up", "k"]),
+            (Action::MoveDown, &["down", "j"]),
+            (Action::AddProperty, &["a"]),
+            (Action::EditProperty, &["e", "enter"]),
+            (Action::DeleteProperty, &["x", "delete"]),
+            (Action::Diff, &["d"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Diff => &[
+            (Action::Close, &["esc", "q"]),
+            (Action::MoveUp, &["up", "k"]),
+            (Action::MoveDown, &["down", "j"]),
+            (Action::PageUp, &["pageup", "ctrl+u"]),
+            (Action::PageDown, &["pagedown", "ctrl+d", "space"]),
+            (Action::ToggleSideBySide, &["s"]),
+            (Action::IgnoreWhitespace, &["w"]),
+            (Action::IgnoreEolStyle, &["e"]),
+            (Action::Help, &["?", "f1"]),
+        ],
+        KeyContext::Prompt => &[
+            (Action::Submit, &["enter"]),
+            (Action::Cancel, &["esc"]),
+            (Action::DeleteChar, &["backspace"]),
+            (Action::Help, &["f1"]),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeyList;
+
+    fn key(modifiers: KeyModifiers, code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn config(ctx: &str, bindings: &[(&str, &str)]) -> KeysConfig {
+        let actions = bindings
+            .iter()
+            .map(|(action, key)| (action.to_string(), KeyList::One(key.to_string())))
+            .collect();
+        KeysConfig::from([(ctx.to_string(), actions)])
+    }
+
+    #[test]
+    fn parse_reads_modifiers_and_names() {
+        assert_eq!(
+            KeyBinding::parse("Ctrl+Alt+x"),
+            Ok(KeyBinding::new(
+                KeyModifiers::CONTROL | KeyModifiers::ALT,
+                KeyCode::Char('x')
+            ))
+        );
+        assert_eq!(
+            KeyBinding::parse("shift+tab"),
+            Ok(KeyBinding::new(KeyModifiers::SHIFT, KeyCode::Tab))
+        );
+        assert_eq!(
+            KeyBinding::parse("space"),
+            Ok(KeyBinding::new(KeyModifiers::NONE, KeyCode::Char(' ')))
+        );
+        assert_eq!(
+            KeyBinding::parse("F5"),
+            Ok(KeyBinding::new(KeyModifiers::NONE, KeyCode::F(5)))
+        );
+        assert!(KeyBinding::parse("hyper+x").is_err());
+        assert!(KeyBinding::parse("f13").is_err());
+        assert!(KeyBinding::parse("xy").is_err());
+    }
+
+    #[test]
+    fn parse_keeps_a_literal_plus() {
+        assert_eq!(
+            KeyBinding::parse("+"),
+            Ok(KeyBinding::new(KeyModifiers::NONE, KeyCode::Char('+')))
+        );
+        assert_eq!(
+            KeyBinding::parse("ctrl++"),
+            Ok(KeyBinding::new(KeyModifiers::CONTROL, KeyCode::Char('+')))
+        );
+        assert_eq!(KeyBinding::parse("ctrl++").unwrap().to_string(), "ctrl++");
+    }
+
+    #[test]
+    fn shifted_letters_match_with_or_without_shift() {
+        let upper = KeyBinding::parse("A").unwrap();
+        assert!(upper.matches(&key(KeyModifiers::SHIFT, KeyCode::Char('A'))));
+        assert!(upper.matches(&key(KeyModifiers::NONE, KeyCode::Char('A'))));
+        assert!(!upper.matches(&key(KeyModifiers::NONE, KeyCode::Char('a'))));
+
+        let alt_upper = KeyBinding::parse("alt+L").unwrap();
+        assert!(alt_upper.matches(&key(
+            KeyModifiers::ALT | KeyModifiers::SHIFT,
+            KeyCode::Char('L')
+        )));
+        assert!(!alt_upper.matches(&key(KeyModifiers::ALT, KeyCode::Char('l'))));
+
+        // Terminals differ in the case they send with ctrl
+        let ctrl = KeyBinding::parse("ctrl+c").unwrap();
+        assert!(ctrl.matches(&key(KeyModifiers::CONTROL, KeyCode::Char('C'))));
+        assert!(!ctrl.matches(&key(KeyModifiers::NONE, KeyCode::Char('c'))));
+    }
+
+    #[test]
+    fn from_config_replaces_the_keys_of_an_action() {
+        let (keymap, errors) = Keymap::from_config(&config("normal", &[("add", "+")]));
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(
+            keymap.keys_for(KeyContext::Normal, Action::Add),
+            vec![KeyBinding::parse("+").unwrap()]
+        );
+        let plus = key(KeyModifiers::NONE, KeyCode::Char('+'));
+        assert_eq!(keymap.action(KeyContext::Normal, &plus), Some(Action::Add));
+        let a = key(KeyModifiers::NONE, KeyCode::Char('a'));
+        assert_eq!(keymap.action(KeyContext::Normal, &a), None);
+    }
+
+    #[test]
+    fn from_config_reports_conflicts_and_keeps_the_defaults() {
+        let (keymap, errors) = Keymap::from_config(&config("normal", &[("add", "q")]));
+        assert_eq!(
+            errors,
+            vec![trf(
+                "keys.conflict",
+                &[&KeyBinding::parse("q").unwrap(), &"quit", &"add", &"normal"]
+            )]
+        );
+        assert_eq!(
+            keymap.keys_for(KeyContext::Normal, Action::Add),
+            vec![KeyBinding::parse("a").unwrap()]
+        );
+    }
+
+    #[test]
+    fn from_config_reports_unknown_names() {
+        let mut config = config("normal", &[("fly", "f"), ("add", "ctrl+nope")]);
+        config.extend(self::config("nowhere", &[("quit", "q")]));
+        let (_, mut errors) = Keymap::from_config(&config);
+        errors.sort();
+        let mut expected = vec![
+            trf("keys.unknown_action", &[&"fly", &"normal"]),
+            trf(
+                "keys.error_in",
+                &[&trf("keys.unknown_key", &[&"ctrl+nope"]), &"normal"],
+            ),
+            trf("keys.unknown_mode", &[&"nowhere"]),
+        ];
+        expected.sort();
+        assert_eq!(errors, expected);
+    }
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        assert!(Keymap::default().conflicts().is_empty());
+    }
+}
@@ -1,61 +1,302 @@
+mod bugtraq;
+mod checks;
+mod config;
 mod cursor;
+mod diff;
+mod editor;
+mod events;
+mod export;
 mod files;
+mod filter;
+mod history;
+mod hooks;
+mod i18n;
+mod keymap;
+mod message;
+mod preview;
 mod renders;
+mod select;
 mod svn;
+mod theme;
+mod tools;
+mod tree;
+mod watcher;
 use crate::{
+    bugtraq::Bugtraq,
+    checks::{Checks, Finding, Severity},
+    config::load_config,
     cursor::{move_cursor_down, move_cursor_up},
-    files::copy_file,
+    diff::{DiffLine, DiffOptions, SideRow, parse_diff, side_by_side},
+    editor::edit_text,
+    events::{AppEvent, Events, InputReader, JobKind, JobResult},
+    export::{ExportFormat, export_status},
+    files::{copy_file, copy_text},
+    filter::filter_entries,
+    history::MessageHistory,
+    hooks::{HookAction, HookStage, Hooks},
+    i18n::{Lang, set_lang, tr, trf},
+    keymap::{Action, KeyBinding, KeyContext, Keymap},
+    message::CommitRules,
+    preview::{Preview, load_preview},
     renders::{
-        BlockRenderStatus, ModalInfo, ModalType, ProjectInfo, create_layout, create_section_commit,
-        create_section_info, create_section_status, create_selected_items, render_confirm_modal,
-        render_modal,
+        BlockRenderStatus, DiffView, IssueField, ModalInfo, ModalType, ProjectInfo, StatusColumns,
+        Toast, create_hint_bar, create_layout, create_section_commit, create_section_info,
+        create_section_status, create_selected_items, render_compare_modal, render_confirm_modal,
+        render_diff_modal, render_help_modal, render_log_picker, render_modal,
+        render_preview_modal, render_prompt_modal, render_properties_modal, render_toasts,
+        spinner_frame, status_prefix_width,
     },
-    svn::SvnClient,
+    select::Selector,
+    svn::{
+        ChangedPath, CommitSummary, CompareTarget, DiffTarget, IgnoreTarget, LockInfo, LogEntry,
+        SvnClient, SvnProperty, SvnState, ignore_rule, is_out_of_date_error, out_of_date_paths,
+    },
+    theme::Theme,
+    tools::{ToolLaunch, ToolsConfig},
+    tree::{StatusRow, flat_rows, tree_rows},
+    watcher::{WatchConfig, Watcher},
 };
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use ratatui::{DefaultTerminal, Frame, widgets::ListState};
+use color_eyre::eyre::{WrapErr, eyre};
+use crossterm::{
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
+    execute,
+};
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Position, Rect},
+    widgets::ListState,
+};
 use std::{
-    fs::canonicalize,
+    collections::{HashMap, HashSet},
+    fs::{self, canonicalize},
+    io,
     path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
 };
 
+/// Redraw interval while a spinner or a toast is on screen
+const TICK: Duration = Duration::from_millis(100);
+const TOAST_DURATION: Duration = Duration::from_secs(3);
+/// Lines the wheel moves the diff view
+const WHEEL_LINES: usize = 3;
+
 #[derive(Parser)]
 #[command(name = "Rustisvn")]
-#[command(
-    about = "Una TUI para proyectos con Svn",
-    long_about = "
-Rustisvn
-
-Una app minimalista y simple para usar en projectos svn.
-
-Funcionalidades disponibles como:
-- Seleccionar archivo
-- Hacer commits
-- Copiar el path del archivo
-
-Lo necesario para un flujo simple y sencillo de trabajo en la rama trunck"
-)]
 struct Args {
     #[arg(short, long, default_value = ".")]
     directory: String,
+    #[arg(short, long, value_enum)]
+    export: Option<ExportFormat>,
+    #[arg(short, long, requires = "export")]
+    output: Option<PathBuf>,
+}
+
+impl Args {
+    /// Parses the command line with the help texts in the active language
+    fn parse_localized() -> Self {
+        let command = Args::command()
+            .about(tr("cli.about"))
+            .long_about(tr("cli.long_about"))
+            .mut_arg("directory", |arg| arg.help(tr("cli.directory")))
+            .mut_arg("export", |arg| arg.help(tr("cli.export")))
+            .mut_arg("output", |arg| arg.help(tr("cli.output")));
+        Args::from_arg_matches(&command.get_matches()).unwrap_or_else(|e| e.exit())
+    }
 }
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
-    let args = Args::parse();
+    let config = load_config();
+    let lang = config
+        .as_ref()
+        .ok()
+        .and_then(|config| config.language.as_deref())
+        .and_then(Lang::from_code)
+        .unwrap_or_else(Lang::from_env);
+    set_lang(lang);
+    let mouse = config
+        .as_ref()
+        .ok()
+        .and_then(|config| config.mouse)
+        .unwrap_or(true);
+    let args = Args::parse_localized();
+    let directory = canonicalize(&args.directory)
+        .wrap_err_with(|| trf("main.directory_failed", &[&args.directory]))?;
+    if let Some(format) = args.export {
+        return run_export(&directory, format, args.output.as_deref());
+    }
+    let (keymap, theme, checks, hooks, commit_rules, watch, tools, config_errors) = match config {
+        Ok(config) => {
+            let (keymap, mut errors) = Keymap::from_config(&config.keys);
+            let (theme, theme_errors) = Theme::from_config(&config.theme);
+            let (checks, check_errors) = Checks::from_config(&config.checks);
+            let (hooks, hook_errors) = Hooks::from_config(&config.hooks);
+            let (commit_rules, commit_errors) = CommitRules::from_config(&config.commit);
+            errors.extend(theme_errors);
+            errors.extend(check_errors);
+            errors.extend(hook_errors);
+            errors.extend(commit_errors);
+            (
+                keymap,
+                theme,
+                checks,
+                hooks,
+                commit_rules,
+                config.watch,
+                config.tools,
+                errors,
+            )
+        }
+        Err(error) => (
+            Keymap::default(),
+            Theme::default(),
+            Checks::default(),
+            Hooks::default(),
+            CommitRules::default(),
+            WatchConfig::default(),
+            ToolsConfig::default(),
+            vec![error],
+        ),
+    };
+    let mut app = App::new(directory, keymap, theme, checks, hooks, commit_rules);
+    app.start_watcher(&watch);
+    app.mouse = mouse;
+    app.tools = tools;
+    if !config_errors.is_empty() {
+        app.show_modal(
+            tr("title.config_error"),
+            trf("msg.config_defaults", &[&config_errors.join("\n")]),
+            ModalType::Warning,
+        );
+    }
+    let terminal = init_terminal(mouse);
+    let result = app.run(terminal);
+    restore_terminal(mouse);
+    result
+}
+
+/// ratatui's terminal setup, reporting mouse events when enabled
+fn init_terminal(mouse: bool) -> DefaultTerminal {
     let terminal = ratatui::init();
-    let directory = canonicalize(&args.directory).unwrap();
-    let result = App::new(directory).run(terminal);
+    if mouse {
+        // Without mouse reporting the keyboard still works
+        let _ = execute!(io::stdout(), EnableMouseCapture);
+    }
+    terminal
+}
+
+fn restore_terminal(mouse: bool) {
+    if mouse {
+        let _ = execute!(io::stdout(), DisableMouseCapture);
+    }
     ratatui::restore();
-    result
 }
 
-#[derive(Debug, Default, PartialEq)]
+/// One line per finding, blockers first, cut to what fits in a modal
+fn format_findings(findings: &[Finding]) -> String {
+    const SHOWN: usize = 10;
+    let mut lines: Vec<String> = findings
+        .iter()
+        .take(SHOWN)
+        .map(|finding| {
+            let level = match finding.severity {
+                Severity::Blocker => tr("check.blocker"),
+                Severity::Warning => tr("check.warning"),
+            };
+            format!(
+                "[{}] {}: {}",
+                level,
+                finding.file.display(),
+                finding.message
+            )
+        })
+        .collect();
+    if findings.len() > SHOWN {
+        lines.push(trf("check.more", &[&(findings.len() - SHOWN)]));
+    }
+    lines.join("\n")
+}
+
+/// Revision, files by kind and hook output of a commit, cut to what fits in a modal
+fn format_commit_summary(summary: &CommitSummary, hook_output: &str) -> String {
+    const SHOWN: usize = 10;
+    let mut lines = vec![match summary.revision {
+        Some(revision) => trf("summary.revision", &[&revision]),
+        None => tr("summary.no_revision").to_string(),
+    }];
+    for (key, files) in [
+        ("summary.sent", &summary.sent),
+        ("summary.added", &summary.added),
+        ("summary.deleted", &summary.deleted),
+    ] {
+        if files.is_empty() {
+            continue;
+        }
+        lines.push(trf(key, &[&files.len()]));
+        lines.extend(
+            files
+                .iter()
+                .take(SHOWN)
+                .map(|file| format!("  {}", file.display())),
+        );
+        if files.len() > SHOWN {
+            lines.push(format!(
+                "  {}",
+                trf("check.more", &[&(files.len() - SHOWN)])
+            ));
+        }
+    }
+    for (key, output) in [
+        ("summary.repository_output", summary.warnings.as_str()),
+        ("summary.hook_output", hook_output),
+    ] {
+        if !output.is_empty() {
+            lines.push(tr(key).to_string());
+            lines.extend(output.lines().map(|line| format!("  {}", line)));
+        }
+    }
+    lines.join("\n")
+}
+
+fn join_keys(keys: &[KeyBinding]) -> String {
+    keys.iter()
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+fn run_export(directory: &Path, format: ExportFormat, output: Option<&Path>) -> Result<()> {
+    let mut svn = SvnClient::new(directory);
+    // An empty export would look like a clean working copy
+    svn.init_svn_status().map_err(|e| eyre!(e))?;
+    let json = export_status(&svn.status, format)?;
+    match output {
+        Some(path) => {
+            fs::write(path, json).wrap_err_with(|| trf("main.write_failed", &[&path.display()]))?
+        }
+        None => print!("{}", json),
+    }
+    Ok(())
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub enum ConfirmMode {
     #[default]
     Revert,
+    DeleteProperty,
+    ForceLock,
+    ForceUnlock,
+    UpdateOutOfDate,
+    /// Pre-commit checks only found warnings
+    CommitAnyway,
+    /// `bugtraq:warnifnoissue` is set and the commit names no issue
+    NoIssue,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -66,6 +307,40 @@ pub enum AppMode {
     Selections,
     Confirm(ConfirmMode),
     Modal(ModalType),
+    Filter,
+    /// Range selection, anchored at the given status row
+    Visual(usize),
+    Prompt(PromptKind),
+    /// Choosing which svn:ignore pattern to add for the current entry
+    Ignore,
+    Properties,
+    Diff,
+    /// Contents of a file that has no diff yet, in the diff viewer's area
+    Preview,
+    /// Picking a message from the author's previous commits
+    LogPicker,
+    /// Result of the last commit, in the modal text
+    CommitSummary,
+    /// Paths changed between two revisions or URLs
+    Compare,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    SelectMatching,
+    PropertyName,
+    LockMessage {
+        force: bool,
+    },
+    /// Revisions or URLs to compare, for the path under the cursor
+    Compare,
+}
+
+/// Text to open in the external editor once the key handler returns
+#[derive(Debug)]
+pub enum EditRequest {
+    Property(SvnProperty),
+    CommitMessage,
 }
 
 #[derive(Debug, Default)]
@@ -76,197 +351,1966 @@ pub struct App {
     block_status: Vec<BlockRenderStatus>,
     mode: AppMode,
     modal: ModalInfo,
+    keymap: Keymap,
+    theme: Theme,
+    show_help: bool,
+    filter: String,
+    tree_view: bool,
+    collapsed: HashSet<PathBuf>,
+    prompt: String,
+    ignore_global: bool,
+    property_file: PathBuf,
+    properties: Vec<SvnProperty>,
+    property_idx: usize,
+    pending_edit: Option<EditRequest>,
+    tools: ToolsConfig,
+    /// External diff or merge tool to run once the key handler returns
+    pending_tool: Option<ToolLaunch>,
+    diff: Vec<DiffLine>,
+    /// The same diff in two columns
+    diff_rows: Vec<SideRow>,
+    diff_target: DiffTarget,
+    diff_options: DiffOptions,
+    side_by_side: bool,
+    diff_title: String,
+    diff_scroll: usize,
+    diff_page: usize,
+    preview: Preview,
+    /// Mode to go back to when the diff viewer closes
+    diff_return: AppMode,
+    /// Lock details already asked to svn, cleared on every refresh
+    locks: HashMap<PathBuf, Option<LockInfo>>,
+    /// Files to update before the commit can go through
+    out_of_date: Vec<PathBuf>,
+    checks: Checks,
+    check_report: String,
+    hooks: Hooks,
+    commit_rules: CommitRules,
+    /// Template last put in the commit message by `next_template`
+    template_idx: usize,
+    history: MessageHistory,
+    log_entries: Vec<LogEntry>,
+    log_idx: usize,
+    bugtraq: Option<Bugtraq>,
+    issue: String,
+    /// Typing goes to the issue field instead of the message
+    issue_focus: bool,
+    committed_revision: Option<u64>,
+    compare: Option<CompareTarget>,
+    compare_paths: Vec<ChangedPath>,
+    compare_idx: usize,
+    watcher: Option<Watcher>,
+    events: Events,
+    /// Background jobs still running
+    jobs: Vec<JobKind>,
+    /// Advances on every tick while a job runs
+    spinner: usize,
+    toasts: Vec<Toast>,
+    /// Files changed while a modal or menu was open, refreshed once it closes
+    status_stale: bool,
+    /// Last failed status load, shown once until a load succeeds again
+    status_error: Option<String>,
+    mouse: bool,
+    /// Panes of the last draw, to map clicks and the wheel
+    panes: Vec<Rect>,
+    /// Yes and no labels of the open confirmation
+    confirm_buttons: Option<(Rect, Rect)>,
 }
 
 impl App {
-    pub fn new<T: AsRef<Path>>(directory: T) -> Self {
+    pub fn new<T: AsRef<Path>>(
+        directory: T,
+        keymap: Keymap,
+        theme: Theme,
+        checks: Checks,
+        hooks: Hooks,
+        commit_rules: CommitRules,
+    ) -> Self {
         let path = directory.as_ref().to_path_buf();
         let mut svn = SvnClient::new(&path);
-        svn.init_svn_status();
+        let status_error = svn.init_svn_status().err();
         let block_status = vec![BlockRenderStatus::new(); 3];
         let modal = ModalInfo::new();
-        Self {
+        // Not every working copy is readable here, the tracker is optional
+        let (bugtraq, bugtraq_errors) = svn
+            .inherited_properties(Path::new("."))
+            .map(|properties| Bugtraq::from_properties(&properties))
+            .unwrap_or_default();
+        let mut app = Self {
             running: true,
             directory: path.clone(),
             svn,
             block_status,
             mode: AppMode::Normal,
             modal,
+            keymap,
+            theme,
+            show_help: false,
+            filter: String::new(),
+            tree_view: false,
+            collapsed: HashSet::new(),
+            prompt: String::new(),
+            ignore_global: false,
+            property_file: PathBuf::new(),
+            properties: Vec::new(),
+            property_idx: 0,
+            pending_edit: None,
+            tools: ToolsConfig::default(),
+            pending_tool: None,
+            diff: Vec::new(),
+            diff_rows: Vec::new(),
+            diff_target: DiffTarget::default(),
+            diff_options: DiffOptions::default(),
+            side_by_side: false,
+            diff_title: String::new(),
+            diff_scroll: 0,
+            diff_page: 0,
+            preview: Preview::default(),
+            diff_return: AppMode::Normal,
+            locks: HashMap::new(),
+            out_of_date: Vec::new(),
+            checks,
+            check_report: String::new(),
+            hooks,
+            commit_rules,
+            template_idx: 0,
+            history: MessageHistory::load(),
+            log_entries: Vec::new(),
+            log_idx: 0,
+            bugtraq,
+            issue: String::new(),
+            issue_focus: false,
+            committed_revision: None,
+            compare: None,
+            compare_paths: Vec::new(),
+            compare_idx: 0,
+            watcher: None,
+            events: Events::default(),
+            jobs: Vec::new(),
+            spinner: 0,
+            toasts: Vec::new(),
+            status_stale: false,
+            status_error: None,
+            mouse: false,
+            panes: Vec::new(),
+            confirm_buttons: None,
+        };
+        if !bugtraq_errors.is_empty() {
+            app.show_modal(
+                tr("title.bugtraq_error"),
+                bugtraq_errors.join("\n"),
+                ModalType::Warning,
+            );
         }
+        if let Some(e) = status_error {
+            app.show_modal(tr("title.status_error"), e, ModalType::Error);
+        }
+        app
+    }
+
+    pub fn show_modal(&mut self, title: &str, message: String, modal_type: ModalType) {
+        self.modal.title = title.to_string();
+        self.modal.message = message;
+        self.mode = AppMode::Modal(modal_type);
+    }
+
+    pub fn start_watcher(&mut self, config: &WatchConfig) {
+        if !config.enabled() {
+            return;
+        }
+        let sender = self.events.sender();
+        let started = Watcher::start(&self.directory, config.debounce(), move || {
+            // The receiver is gone only while the app shuts down
+            let _ = sender.send(AppEvent::FilesChanged);
+        });
+        match started {
+            Ok(watcher) => self.watcher = Some(watcher),
+            Err(e) => self.show_modal(tr("title.watch_error"), e, ModalType::Warning),
+        }
+    }
+
+    /// Whether the status rows can change under the current mode; menus and
+    /// dialogs hold row indices
+    fn can_refresh(&self) -> bool {
+        matches!(
+            self.mode,
+            AppMode::Normal | AppMode::Selections | AppMode::Commit | AppMode::Filter
+        )
+    }
+
+    /// Reloads the status in the background, or once the current dialog closes
+    fn refresh(&mut self) {
+        if !self.can_refresh() {
+            self.status_stale = true;
+            return;
+        }
+        self.status_stale = false;
+        if self.jobs.contains(&JobKind::Status) {
+            // The running job may have started before the latest change
+            self.status_stale = true;
+            return;
+        }
+        let svn = self.svn.detached();
+        let generation = self.svn.generation;
+        let remote = svn.remote;
+        self.spawn_job(JobKind::Status, move || JobResult::Status {
+            generation,
+            remote,
+            entries: svn.svn_status(),
+        });
+    }
+
+    fn spawn_job<F>(&mut self, kind: JobKind, work: F)
+    where
+        F: FnOnce() -> JobResult + Send + 'static,
+    {
+        self.jobs.push(kind);
+        let sender = self.events.sender();
+        thread::spawn(move || {
+            let _ = sender.send(AppEvent::Job(work()));
+        });
+    }
+
+    fn finish_job(&mut self, result: JobResult) {
+        if let Some(position) = self.jobs.iter().position(|kind| *kind == result.kind()) {
+            self.jobs.remove(position);
+        }
+        match result {
+            JobResult::Status {
+                generation,
+                remote,
+                entries,
+            } => {
+                // A newer status was loaded meanwhile, the repository was
+                // toggled, or the rows are in use
+                if generation != self.svn.generation
+                    || remote != self.svn.remote
+                    || !self.can_refresh()
+                {
+                    self.status_stale = true;
+                    return;
+                }
+                match entries {
+                    Ok(entries) => {
+                        self.status_error = None;
+                        self.locks.clear();
+                        self.svn.set_entries(entries);
+                    }
+                    // Without the repository the local status still loads
+                    Err(e) if remote => {
+                        self.svn.remote = false;
+                        self.status_stale = true;
+                        self.show_modal(
+                            tr("title.remote_error"),
+                            trf("msg.remote_off", &[&e]),
+                            ModalType::Error,
+                        );
+                    }
+                    // The last good entries and their selections stay
+                    Err(e) => {
+                        if self.status_error.as_ref() != Some(&e) {
+                            self.status_error = Some(e.clone());
+                            self.show_modal(tr("title.status_error"), e, ModalType::Error);
+                        }
+                    }
+                }
+            }
+            JobResult::Lock {
+                generation,
+                file,
+                info,
+            } => {
+                // The locks were cleared by a refresh since the job started
+                if generation == self.svn.generation {
+                    self.locks.insert(file, info);
+                }
+            }
+            JobResult::Log(result) => {
+                if self.mode != AppMode::Commit {
+                    return;
+                }
+                match result {
+                    Ok(entries) => {
+                        self.log_entries = entries;
+                        self.log_idx = 0;
+                        self.mode = AppMode::LogPicker;
+                    }
+                    Err(e) => self.show_modal(tr("title.log_error"), e, ModalType::Error),
+                }
+            }
+            JobResult::Compare { target, paths } => {
+                if self.mode != AppMode::Normal {
+                    return;
+                }
+                match paths {
+                    Ok(paths) => {
+                        self.compare = Some(target);
+                        self.compare_paths = paths;
+                        self.compare_idx = 0;
+                        self.mode = AppMode::Compare;
+                    }
+                    Err(e) => self.show_modal(tr("title.compare_error"), e, ModalType::Error),
+                }
+            }
+            JobResult::Preview { file, preview } => {
+                // Only opens over the list it was asked from
+                if self.mode != AppMode::Normal {
+                    return;
+                }
+                match preview {
+                    Ok(preview) => {
+                        self.preview = preview;
+                        self.diff_title = trf("title.preview", &[&file.display()]);
+                        self.diff_scroll = 0;
+                        self.diff_return = std::mem::replace(&mut self.mode, AppMode::Preview);
+                    }
+                    Err(e) => self.show_modal(tr("title.preview_error"), e, ModalType::Error),
+                }
+            }
+        }
+    }
+
+    fn show_toast(&mut self, text: String) {
+        self.toasts.push(Toast {
+            text,
+            expires: Instant::now() + TOAST_DURATION,
+        });
+    }
+
+    /// Spinners and toasts need redraws even when nothing else happens
+    fn tick_interval(&self) -> Option<Duration> {
+        (!self.jobs.is_empty() || !self.toasts.is_empty()).then_some(TICK)
+    }
+
+    fn on_event(&mut self, event: AppEvent) -> Result<()> {
+        match event {
+            AppEvent::Input(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                self.on_key_event(key)
+            }
+            AppEvent::Input(Event::Mouse(mouse)) => self.on_mouse_event(mouse),
+            // Resizes only need the redraw that follows every event
+            AppEvent::Input(_) => {}
+            AppEvent::InputError(e) => return Err(e.into()),
+            AppEvent::Tick => self.spinner = self.spinner.wrapping_add(1),
+            AppEvent::FilesChanged => self.refresh(),
+            AppEvent::Job(result) => self.finish_job(result),
+        }
+        let now = Instant::now();
+        self.toasts.retain(|toast| toast.expires > now);
+        if self.status_stale && self.can_refresh() {
+            self.refresh();
+        }
+        Ok(())
     }
 
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
         self.running = true;
+        let input = InputReader::start(self.events.sender());
         while self.running {
+            self.load_cursor_lock();
             terminal.draw(|frame| self.render(frame))?;
-            self.handle_crossterm_events()?;
+            let event = self.events.next(self.tick_interval());
+            self.on_event(event)?;
+            if let Some(request) = self.pending_edit.take() {
+                let initial = match &request {
+                    EditRequest::Property(property) => property.value.clone(),
+                    EditRequest::CommitMessage => self.svn.status.commit_message().to_string(),
+                };
+                // The editor needs the terminal back in its normal state
+                input.pause();
+                restore_terminal(self.mouse);
+                let edited = edit_text(&initial);
+                terminal = init_terminal(self.mouse);
+                input.resume();
+                match request {
+                    EditRequest::Property(property) => self.finish_property_edit(property, edited),
+                    EditRequest::CommitMessage => match edited {
+                        Ok(message) => self.svn.status.set_commit_message(message),
+                        Err(e) => self.show_modal(tr("title.editor_error"), e, ModalType::Error),
+                    },
+                }
+            }
+            if let Some(launch) = self.pending_tool.take() {
+                input.pause();
+                restore_terminal(self.mouse);
+                let result = launch.run(&self.directory);
+                terminal = init_terminal(self.mouse);
+                input.resume();
+                match result {
+                    // A merge rewrites the working file
+                    Ok(()) => self.refresh(),
+                    Err(e) => self.show_modal(tr("title.tool_error"), e, ModalType::Error),
+                }
+            }
         }
         Ok(())
     }
 
     fn render(&mut self, frame: &mut Frame) {
-        let layout = create_layout(&frame);
-        let info = ProjectInfo::new(self.directory.to_string_lossy().to_string());
-        let info_section = create_section_info(&info);
-        let mut state = ListState::default().with_selected(Some(self.block_status[0].idx_selected));
-        let mut state_selected_list =
-            ListState::default().with_selected(Some(self.block_status[1].idx_selected));
-        let status_section =
-            create_section_status(&self.svn.status, false, self.mode == AppMode::Normal);
-        let selected_list =
-            create_selected_items(&self.svn.status, false, self.mode == AppMode::Selections);
+        let layout = create_layout(frame);
+        let mut info = ProjectInfo::new(self.directory.to_string_lossy().to_string());
+        info.remote = self.svn.remote;
+        info.lock = self.cursor_lock_text();
+        info.busy = self
+            .jobs
+            .first()
+            .map(|job| format!("{} {}", spinner_frame(self.spinner), job.label()));
+        let info_section = create_section_info(&info, &self.theme);
+        let rows = self.visible_rows();
+        let last_row = rows.len().saturating_sub(1);
+        self.block_status[0].idx_selected = self.block_status[0].idx_selected.min(last_row);
+        // Starting from the last offset keeps the list still while the cursor moves
+        let mut state = ListState::default()
+            .with_offset(self.block_status[0].offset)
+            .with_selected(Some(self.block_status[0].idx_selected));
+        let mut state_selected_list = ListState::default()
+            .with_offset(self.block_status[1].offset)
+            .with_selected(Some(self.block_status[1].idx_selected));
+        let status_section = create_section_status(
+            &self.svn.status,
+            &rows,
+            &self.filter,
+            self.visual_range(),
+            &self.theme,
+            false,
+            matches!(
+                self.mode,
+                AppMode::Normal | AppMode::Filter | AppMode::Visual(_)
+            ),
+        );
+        let selected_list = create_selected_items(
+            &self.svn.status,
+            &self.theme,
+            false,
+            self.mode == AppMode::Selections,
+        );
         let commit_section = create_section_commit(
-            &self.svn.status.commit_message(),
+            self.svn.status.commit_message(),
+            self.commit_rules.summary_max,
+            self.bugtraq.as_ref().map(|bugtraq| IssueField {
+                label: &bugtraq.label,
+                value: &self.issue,
+                focused: self.issue_focus,
+            }),
+            &self.theme,
             self.block_status[2].error,
             self.mode == AppMode::Commit,
         );
         frame.render_widget(info_section, layout[0]);
         frame.render_stateful_widget(status_section, layout[1], &mut state);
         frame.render_stateful_widget(selected_list, layout[2], &mut state_selected_list);
+        self.block_status[0].offset = state.offset();
+        self.block_status[1].offset = state_selected_list.offset();
+        self.panes = layout.clone();
+        self.confirm_buttons = None;
         frame.render_widget(commit_section, layout[3]);
+        let hints = self.hints();
+        frame.render_widget(create_hint_bar(&hints, &self.theme), layout[4]);
 
+        if matches!(
+            self.mode,
+            AppMode::Properties
+                | AppMode::Prompt(PromptKind::PropertyName)
+                | AppMode::Confirm(ConfirmMode::DeleteProperty)
+        ) {
+            render_properties_modal(
+                frame,
+                &self.theme,
+                &trf("title.properties", &[&self.property_file.display()]),
+                &self.properties,
+                self.property_idx,
+            );
+        }
+        if self.mode == AppMode::LogPicker {
+            render_log_picker(
+                frame,
+                &self.theme,
+                &trf("title.log_picker", &[&self.log_entries.len()]),
+                &self.log_entries,
+                self.log_idx,
+                self.bugtraq.as_ref(),
+            );
+        }
+        if self.mode == AppMode::Compare
+            && let Some(target) = &self.compare
+        {
+            render_compare_modal(
+                frame,
+                &self.theme,
+                &trf(
+                    "title.compare_result",
+                    &[&target.label(), &self.compare_paths.len()],
+                ),
+                &self.compare_paths,
+                self.compare_idx,
+            );
+        }
+        if self.mode == AppMode::Diff {
+            let view = if self.side_by_side {
+                DiffView::SideBySide(&self.diff_rows)
+            } else {
+                DiffView::Unified(&self.diff)
+            };
+            self.diff_page = render_diff_modal(
+                frame,
+                &self.theme,
+                &self.diff_title,
+                view,
+                self.diff_options,
+                self.diff_scroll,
+            );
+        }
+        if self.mode == AppMode::Preview {
+            self.diff_page = render_preview_modal(
+                frame,
+                &self.theme,
+                &self.diff_title,
+                &self.preview,
+                self.diff_scroll,
+            );
+        }
         if let AppMode::Confirm(confirm_type) = &self.mode {
             let (title, message) = match confirm_type {
                 ConfirmMode::Revert => (
-                    " Confirmar Revertir ",
-                    "¿Estás seguro de que quieres revertir los cambios?",
+                    tr("title.confirm_revert"),
+                    tr("msg.confirm_revert").to_string(),
+                ),
+                ConfirmMode::DeleteProperty => (
+                    tr("title.confirm_delete_property"),
+                    trf(
+                        "msg.confirm_delete_property",
+                        &[&self.current_property_name()],
+                    ),
                 ),
+                ConfirmMode::ForceLock => (
+                    tr("title.confirm_force_lock"),
+                    trf("msg.confirm_force_lock", &[&self.target_files().len()]),
+                ),
+                ConfirmMode::ForceUnlock => (
+                    tr("title.confirm_force_unlock"),
+                    trf("msg.confirm_force_unlock", &[&self.target_files().len()]),
+                ),
+                ConfirmMode::CommitAnyway => (tr("title.checks_warn"), self.check_report.clone()),
+                ConfirmMode::NoIssue => (
+                    tr("title.no_issue"),
+                    trf(
+                        "msg.no_issue",
+                        &[&self.bugtraq.as_ref().map_or("", |bugtraq| &bugtraq.label)],
+                    ),
+                ),
+                ConfirmMode::UpdateOutOfDate => {
+                    let files: Vec<String> = self
+                        .out_of_date
+                        .iter()
+                        .map(|file| file.display().to_string())
+                        .collect();
+                    (
+                        tr("title.confirm_update"),
+                        trf("msg.confirm_update", &[&files.join("\n")]),
+                    )
+                }
             };
-            render_confirm_modal(frame, title, message);
+            let (yes_key, no_key) = if matches!(
+                confirm_type,
+                ConfirmMode::CommitAnyway | ConfirmMode::NoIssue
+            ) {
+                ("modal.commit_anyway", "modal.cancel")
+            } else {
+                ("modal.yes", "modal.no")
+            };
+            let yes_label = trf(
+                yes_key,
+                &[&join_keys(
+                    &self.keymap.keys_for(KeyContext::Confirm, Action::Confirm),
+                )],
+            );
+            let no_label = trf(
+                no_key,
+                &[&join_keys(
+                    &self.keymap.keys_for(KeyContext::Confirm, Action::Cancel),
+                )],
+            );
+            self.confirm_buttons = Some(render_confirm_modal(
+                frame,
+                &self.theme,
+                title,
+                &message,
+                &yes_label,
+                &no_label,
+            ));
         }
         if let AppMode::Modal(modal_type) = &self.mode {
             render_modal(
                 frame,
+                &self.theme,
                 &self.modal.title,
                 &self.modal.message,
                 modal_type.clone(),
             );
         }
+        if self.mode == AppMode::CommitSummary {
+            render_modal(
+                frame,
+                &self.theme,
+                &self.modal.title,
+                &self.modal.message,
+                ModalType::Info,
+            );
+        }
+        if let AppMode::Prompt(kind) = &self.mode {
+            let (title, message) = match kind {
+                PromptKind::SelectMatching => (
+                    tr("title.select_matching"),
+                    tr("msg.select_matching").to_string(),
+                ),
+                PromptKind::PropertyName => {
+                    (tr("title.add_property"), tr("msg.add_property").to_string())
+                }
+                PromptKind::LockMessage { .. } => (
+                    tr("title.lock"),
+                    trf("msg.lock", &[&self.target_files().len()]),
+                ),
+                PromptKind::Compare => {
+                    let path = self.current_file().unwrap_or_else(|| PathBuf::from("."));
+                    (tr("title.compare"), trf("msg.compare", &[&path.display()]))
+                }
+            };
+            render_prompt_modal(frame, &self.theme, title, &message, &self.prompt);
+        }
+        if self.mode == AppMode::Ignore {
+            let menu = self.ignore_menu_rows();
+            let rows: Vec<(String, &str)> = menu
+                .iter()
+                .map(|(keys, text)| (keys.clone(), text.as_str()))
+                .collect();
+            render_help_modal(frame, &self.theme, tr("title.ignore"), &rows);
+        }
+        if self.show_help {
+            let ctx = self.mode_context();
+            let rows: Vec<(String, &str)> = self
+                .keymap
+                .grouped(ctx)
+                .into_iter()
+                .map(|(action, keys)| (join_keys(&keys), action.description()))
+                .collect();
+            render_help_modal(
+                frame,
+                &self.theme,
+                &trf("title.help", &[&ctx.title()]),
+                &rows,
+            );
+        }
+        render_toasts(frame, &self.theme, &self.toasts);
     }
 
-    fn handle_crossterm_events(&mut self) -> Result<()> {
-        match event::read()? {
-            Event::Key(key) if key.kind == KeyEventKind::Press => self.on_key_event(key),
-            Event::Mouse(_) => {}
-            Event::Resize(_, _) => {}
-            _ => {}
+    fn hints(&self) -> Vec<(String, &'static str)> {
+        let ctx = self.key_context();
+        ctx.hint_actions()
+            .iter()
+            .filter_map(|&action| {
+                let keys = self.keymap.keys_for(ctx, action);
+                keys.first()
+                    .map(|key| (key.to_string(), action.short_description()))
+            })
+            .collect()
+    }
+
+    fn key_context(&self) -> KeyContext {
+        if self.show_help {
+            KeyContext::Help
+        } else {
+            self.mode_context()
         }
-        Ok(())
+    }
+
+    fn mode_context(&self) -> KeyContext {
+        match self.mode {
+            AppMode::Normal => KeyContext::Normal,
+            AppMode::Commit => KeyContext::Commit,
+            AppMode::Selections => KeyContext::Selections,
+            AppMode::Confirm(_) => KeyContext::Confirm,
+            AppMode::Modal(_) => KeyContext::Modal,
+            AppMode::Filter => KeyContext::Filter,
+            AppMode::Visual(_) => KeyContext::Visual,
+            AppMode::Prompt(_) => KeyContext::Prompt,
+            AppMode::Ignore => KeyContext::Ignore,
+            AppMode::Properties => KeyContext::Properties,
+            AppMode::Diff | AppMode::Preview => KeyContext::Diff,
+            AppMode::LogPicker => KeyContext::LogPicker,
+            AppMode::Compare => KeyContext::Compare,
+            AppMode::CommitSummary => KeyContext::CommitSummary,
+        }
+    }
+
+    fn ignore_property(&self) -> &'static str {
+        if self.ignore_global {
+            "svn:global-ignores"
+        } else {
+            "svn:ignore"
+        }
+    }
+
+    /// Rows of the ignore menu, showing the pattern each key would add
+    fn ignore_menu_rows(&self) -> Vec<(String, String)> {
+        let file = self
+            .current_entry_idx()
+            .and_then(|idx| self.svn.status.entries.get(idx))
+            .map(|entry| entry.file.clone())
+            .unwrap_or_default();
+        let targets = [
+            (Action::IgnoreFile, IgnoreTarget::File, "ignore.file"),
+            (
+                Action::IgnoreExtension,
+                IgnoreTarget::Extension,
+                "ignore.extension",
+            ),
+            (
+                Action::IgnoreDirectory,
+                IgnoreTarget::Directory,
+                "ignore.directory",
+            ),
+        ];
+        let mut rows: Vec<(String, String)> = targets
+            .iter()
+            .map(|&(action, target, key)| {
+                let keys = join_keys(&self.keymap.keys_for(KeyContext::Ignore, action));
+                let text = match ignore_rule(&file, target) {
+                    Some((dir, pattern)) => {
+                        let dir = if dir.as_os_str().is_empty() {
+                            ".".to_string()
+                        } else {
+                            dir.to_string_lossy().into_owned()
+                        };
+                        format!(
+                            "{} {}",
+                            trf(key, &[&pattern]),
+                            trf("ignore.into", &[&self.ignore_property(), &dir])
+                        )
+                    }
+                    None => format!("{} {}", trf(key, &[&""]), tr("ignore.unavailable")),
+                };
+                (keys, text)
+            })
+            .collect();
+        let state = if self.ignore_global {
+            tr("ignore.on")
+        } else {
+            tr("ignore.off")
+        };
+        rows.push((
+            join_keys(
+                &self
+                    .keymap
+                    .keys_for(KeyContext::Ignore, Action::ToggleGlobal),
+            ),
+            trf("ignore.global", &[&state]),
+        ));
+        rows.push((
+            join_keys(&self.keymap.keys_for(KeyContext::Ignore, Action::Cancel)),
+            Action::Cancel.description().to_string(),
+        ));
+        rows
+    }
+
+    fn ignore_current(&mut self, target: IgnoreTarget) {
+        let Some(idx) = self.current_entry_idx() else {
+            self.mode = AppMode::Normal;
+            return;
+        };
+        let property = self.ignore_property();
+        match self.svn.add_to_ignore(idx, target, self.ignore_global) {
+            Ok((pattern, dir)) => self.show_modal(
+                tr("title.ignored"),
+                trf("msg.ignored", &[&pattern, &property, &dir.display()]),
+                ModalType::Info,
+            ),
+            Err(e) => self.show_modal(tr("title.ignore_error"), e, ModalType::Error),
+        }
+    }
+
+    fn current_file(&self) -> Option<PathBuf> {
+        self.current_entry_idx()
+            .and_then(|idx| self.svn.status.entries.get(idx))
+            .map(|entry| entry.file.clone())
+    }
+
+    /// Lock and update work on the selection, or on the cursor entry when nothing is selected
+    fn target_files(&self) -> Vec<PathBuf> {
+        let files = self.svn.status.selected_files();
+        if files.is_empty() {
+            self.current_file().into_iter().collect()
+        } else {
+            files
+        }
+    }
+
+    /// Our own locks are known locally, anyone else's needs the repository
+    fn load_cursor_lock(&mut self) {
+        let Some(entry) = self
+            .current_entry_idx()
+            .and_then(|idx| self.svn.status.entries.get(idx))
+        else {
+            return;
+        };
+        let Some(lock) = entry.lock else {
+            return;
+        };
+        // One at a time: after a fast scroll only the row the cursor stops on
+        // is asked for, on the next pass once this one is back
+        if self.locks.contains_key(&entry.file) || self.jobs.contains(&JobKind::Lock) {
+            return;
+        }
+        let file = entry.file.clone();
+        let svn = self.svn.detached();
+        let generation = self.svn.generation;
+        // Someone else's lock is only known to the repository, a round-trip
+        self.spawn_job(JobKind::Lock, move || JobResult::Lock {
+            info: svn.lock_info(&file, lock != 'K').ok().flatten(),
+            generation,
+            file,
+        });
+    }
+
+    fn cursor_lock_text(&self) -> Option<String> {
+        let entry = self
+            .current_entry_idx()
+            .and_then(|idx| self.svn.status.entries.get(idx))?;
+        entry.lock?;
+        let Some(info) = self.locks.get(&entry.file) else {
+            return Some(tr("info.lock_loading").to_string());
+        };
+        let info = info.as_ref()?;
+        // `2024-05-02T10:20:30.123456Z` is shown as `2024-05-02 10:20`
+        let created: String = info
+            .created
+            .replacen('T', " ", 1)
+            .chars()
+            .take(16)
+            .collect();
+        Some(trf(
+            "info.lock",
+            &[&info.owner, &created, &info.comment.trim()],
+        ))
+    }
+
+    fn lock_files(&mut self, message: &str, force: bool) {
+        let files = self.target_files();
+        if files.is_empty() {
+            self.mode = AppMode::Normal;
+            return;
+        }
+        self.locks.clear();
+        match self.svn.lock(&files, message, force) {
+            Ok(()) => self.show_modal(
+                tr("title.locked"),
+                trf("msg.locked", &[&files.len()]),
+                ModalType::Info,
+            ),
+            Err(e) => self.show_modal(tr("title.lock_error"), e, ModalType::Error),
+        }
+    }
+
+    fn unlock_files(&mut self, force: bool) {
+        let files = self.target_files();
+        if files.is_empty() {
+            self.mode = AppMode::Normal;
+            return;
+        }
+        self.locks.clear();
+        match self.svn.unlock(&files, force) {
+            Ok(()) => self.show_modal(
+                tr("title.unlocked"),
+                trf("msg.unlocked", &[&files.len()]),
+                ModalType::Info,
+            ),
+            Err(e) => self.show_modal(tr("title.lock_error"), e, ModalType::Error),
+        }
+    }
+
+    fn update_files(&mut self, files: Vec<PathBuf>, after_commit_error: bool) {
+        if files.is_empty() {
+            self.mode = AppMode::Normal;
+            return;
+        }
+        if !self.run_hook(HookStage::Pre, HookAction::Update, &files, "") {
+            return;
+        }
+        self.locks.clear();
+        let result = self.svn.update(&files);
+        if result.is_ok() && !self.run_hook(HookStage::Post, HookAction::Update, &files, "") {
+            return;
+        }
+        match result {
+            Ok(()) if after_commit_error => self.show_modal(
+                tr("title.updated"),
+                trf("msg.updated_commit_again", &[&files.len()]),
+                ModalType::Info,
+            ),
+            Ok(()) => self.show_modal(
+                tr("title.updated"),
+                trf("msg.updated", &[&files.len()]),
+                ModalType::Info,
+            ),
+            Err(e) => self.show_modal(tr("title.update_error"), e, ModalType::Error),
+        }
+    }
+
+    /// Selected files that `svn status -u` already knows are out of date
+    fn stale_selection(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self
+            .svn
+            .status
+            .selections
+            .iter()
+            .filter_map(|&idx| self.svn.status.entries.get(idx))
+            .filter(|entry| entry.out_of_date)
+            .map(|entry| entry.file.clone())
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Starts an empty message from the first template, if there is one
+    fn enter_commit_mode(&mut self) {
+        self.mode = AppMode::Commit;
+        self.block_status[2].error = false;
+        if self.svn.status.commit_message().trim().is_empty()
+            && let Some(template) = self.commit_rules.templates.first()
+        {
+            self.template_idx = 0;
+            self.svn.status.set_commit_message(template.clone());
+        }
+    }
+
+    fn next_template(&mut self) {
+        let templates = &self.commit_rules.templates;
+        if templates.is_empty() {
+            return;
+        }
+        self.template_idx = (self.template_idx + 1) % templates.len();
+        self.svn
+            .status
+            .set_commit_message(templates[self.template_idx].clone());
+    }
+
+    /// The message as it is committed, with the issue line when there is one
+    fn full_commit_message(&self) -> String {
+        let message = self.svn.status.commit_message();
+        match &self.bugtraq {
+            Some(bugtraq) => bugtraq.apply(message, &self.issue),
+            None => message.to_string(),
+        }
+    }
+
+    /// Validates the message, then runs the pre-commit checks; blockers stop
+    /// the commit, warnings ask first
+    fn commit(&mut self) {
+        let problems = self.commit_rules.validate(&self.full_commit_message());
+        if !problems.is_empty() {
+            self.block_status[2].error = true;
+            self.show_modal(
+                tr("title.invalid_message"),
+                problems.join("\n"),
+                ModalType::Error,
+            );
+            return;
+        }
+        if let Some(bugtraq) = &self.bugtraq
+            && bugtraq.warn_if_no_issue
+            && !bugtraq.has_issue(self.svn.status.commit_message(), &self.issue)
+        {
+            self.mode = AppMode::Confirm(ConfirmMode::NoIssue);
+            return;
+        }
+        self.run_checks();
+    }
+
+    fn run_checks(&mut self) {
+        let entries: Vec<_> = self
+            .svn
+            .status
+            .selections
+            .iter()
+            .filter_map(|&idx| self.svn.status.entries.get(idx))
+            .collect();
+        let findings = self.checks.run(&self.directory, &entries, |entry| {
+            self.svn.added_lines(entry)
+        });
+        if findings.is_empty() {
+            self.commit_checked();
+        } else if findings[0].severity == Severity::Blocker {
+            self.block_status[2].error = true;
+            self.show_modal(
+                tr("title.checks_failed"),
+                format_findings(&findings),
+                ModalType::Error,
+            );
+        } else {
+            self.check_report = format_findings(&findings);
+            self.mode = AppMode::Confirm(ConfirmMode::CommitAnyway);
+        }
+    }
+
+    fn commit_checked(&mut self) {
+        let stale = self.stale_selection();
+        if !stale.is_empty() {
+            self.out_of_date = stale;
+            self.mode = AppMode::Confirm(ConfirmMode::UpdateOutOfDate);
+            return;
+        }
+        let files = self.svn.status.selected_files();
+        let typed = self.svn.status.commit_message().to_string();
+        let message = self.full_commit_message();
+        if !self.run_hook(HookStage::Pre, HookAction::Commit, &files, &message) {
+            self.block_status[2].error = true;
+            return;
+        }
+        match self.svn.push_basic_commit(&message) {
+            Ok(summary) => {
+                self.block_status[2].error = false;
+                self.svn.status.clear_commit_message();
+                self.issue.clear();
+                self.issue_focus = false;
+                // A failing post-commit hook is reported in the summary, the
+                // revision is already in the repository
+                let hook_output = self
+                    .hooks
+                    .run(
+                        HookStage::Post,
+                        HookAction::Commit,
+                        &self.directory,
+                        &files,
+                        &message,
+                    )
+                    .unwrap_or_else(|e| e);
+                let mut text = format_commit_summary(&summary, &hook_output);
+                if let Err(e) = self.history.push(&typed) {
+                    text.push_str(&format!("\n{}", e));
+                }
+                self.committed_revision = summary.revision;
+                self.modal.title = match summary.revision {
+                    Some(revision) => trf("title.committed", &[&revision]),
+                    None => tr("title.commit").to_string(),
+                };
+                self.modal.message = text;
+                self.mode = AppMode::CommitSummary;
+            }
+            // The message is kept, the commit is retried after updating
+            Err(error_message) if is_out_of_date_error(&error_message) => {
+                let mut files = out_of_date_paths(&error_message, &self.directory);
+                if files.is_empty() {
+                    files = self.svn.status.selected_files();
+                }
+                self.out_of_date = files;
+                self.mode = AppMode::Confirm(ConfirmMode::UpdateOutOfDate);
+            }
+            Err(error_message) => {
+                self.block_status[2].error = true;
+                self.show_modal(tr("title.commit_error"), error_message, ModalType::Error);
+            }
+        }
+    }
+
+    fn open_log_picker(&mut self) {
+        const LOG_PICKER_LIMIT: usize = 50;
+        let Some(author) = self.commit_rules.author.clone() else {
+            self.show_modal(
+                tr("title.log_error"),
+                tr("svn.no_author").to_string(),
+                ModalType::Error,
+            );
+            return;
+        };
+        if self.jobs.contains(&JobKind::Log) {
+            return;
+        }
+        let svn = self.svn.detached();
+        self.spawn_job(JobKind::Log, move || {
+            JobResult::Log(svn.log_by_author(&author, LOG_PICKER_LIMIT))
+        });
+    }
+
+    /// A failing pre-hook shows its output and returns false so the action is
+    /// skipped; a failing post-hook only warns, the action already happened.
+    fn run_hook(
+        &mut self,
+        stage: HookStage,
+        action: HookAction,
+        files: &[PathBuf],
+        message: &str,
+    ) -> bool {
+        match self
+            .hooks
+            .run(stage, action, &self.directory, files, message)
+        {
+            Ok(_) => true,
+            Err(e) => {
+                match stage {
+                    HookStage::Pre => self.show_modal(tr("title.hook_failed"), e, ModalType::Error),
+                    HookStage::Post => {
+                        self.show_modal(tr("title.hook_warning"), e, ModalType::Warning)
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    fn current_property_name(&self) -> String {
+        self.properties
+            .get(self.property_idx)
+            .map(|property| property.name.clone())
+            .unwrap_or_default()
+    }
+
+    fn open_properties(&mut self, file: PathBuf) {
+        self.property_file = file;
+        self.property_idx = 0;
+        self.reload_properties();
+    }
+
+    fn reload_properties(&mut self) {
+        match self.svn.properties(&self.property_file) {
+            Ok(properties) => {
+                self.properties = properties;
+                self.property_idx = self
+                    .property_idx
+                    .min(self.properties.len().saturating_sub(1));
+                self.mode = AppMode::Properties;
+            }
+            Err(e) => self.show_modal(tr("title.property_error"), e, ModalType::Error),
+        }
+    }
+
+    fn finish_property_edit(&mut self, property: SvnProperty, edited: Result<String, String>) {
+        let result = edited.and_then(|value| {
+            let unchanged =
+                value == property.value && self.properties.iter().any(|p| p.name == property.name);
+            if unchanged {
+                Ok(())
+            } else {
+                self.svn
+                    .set_property(&self.property_file, &property.name, &value)
+            }
+        });
+        match result {
+            Ok(()) => {
+                self.reload_properties();
+                if let Some(idx) = self.properties.iter().position(|p| p.name == property.name) {
+                    self.property_idx = idx;
+                }
+            }
+            Err(e) => self.show_modal(tr("title.property_error"), e, ModalType::Error),
+        }
+    }
+
+    fn open_diff(&mut self, file: PathBuf) {
+        let title = trf("title.diff", &[&file.display()]);
+        self.diff_target = DiffTarget::WorkingCopy(file);
+        if self.load_diff() {
+            self.diff_title = title;
+            self.diff_return = std::mem::replace(&mut self.mode, AppMode::Diff);
+        }
+    }
+
+    /// Runs `svn diff` on `diff_target` with the current options, false when it failed
+    fn load_diff(&mut self) -> bool {
+        match self.svn.diff(&self.diff_target, self.diff_options) {
+            Ok(text) => {
+                self.diff = parse_diff(&text);
+                self.diff_rows = side_by_side(&self.diff);
+                self.diff_scroll = 0;
+                true
+            }
+            Err(e) => {
+                self.show_modal(tr("title.diff_error"), e, ModalType::Error);
+                false
+            }
+        }
+    }
+
+    fn start_compare(&mut self, input: &str) {
+        self.mode = AppMode::Normal;
+        if input.trim().is_empty() {
+            return;
+        }
+        let path = self.current_file().unwrap_or_else(|| PathBuf::from("."));
+        let target = match CompareTarget::parse(input, &path) {
+            Ok(target) => target,
+            Err(e) => {
+                self.show_modal(tr("title.compare_error"), e, ModalType::Error);
+                return;
+            }
+        };
+        if self.jobs.contains(&JobKind::Compare) {
+            return;
+        }
+        let svn = self.svn.detached();
+        self.spawn_job(JobKind::Compare, move || JobResult::Compare {
+            paths: svn.summarize(&target),
+            target,
+        });
+    }
+
+    /// Diff of the path under the cursor; closing it goes back to the list
+    fn open_compare_diff(&mut self) {
+        let (Some(target), Some(changed)) =
+            (&self.compare, self.compare_paths.get(self.compare_idx))
+        else {
+            return;
+        };
+        let title = trf("title.compare_diff", &[&changed.path, &target.label()]);
+        self.diff_target = DiffTarget::Compare {
+            target: target.clone(),
+            path: changed.path.clone(),
+        };
+        if self.load_diff() {
+            self.diff_title = title;
+            self.diff_return = std::mem::replace(&mut self.mode, AppMode::Diff);
+        }
+    }
+
+    /// Conflicted files go to the merge tool with the files svn left, the
+    /// rest to the diff tool against their pristine copy
+    fn prepare_external_tool(&mut self) {
+        let Some(entry) = self
+            .current_entry_idx()
+            .and_then(|idx| self.svn.status.entries.get(idx))
+        else {
+            return;
+        };
+        let file = entry.file.clone();
+        let launch = match entry.kind() {
+            SvnState::Conflicted => match self.svn.conflict_files(&file) {
+                Ok(Some(conflict)) => {
+                    ToolLaunch::merge(&self.tools, &self.directory, &file, &conflict)
+                }
+                Ok(None) => Err(trf("tools.no_conflict_files", &[&file.display()])),
+                Err(e) => Err(e),
+            },
+            kind => {
+                // A copied file is compared with its copy source, only plain
+                // adds and unversioned files have no pristine copy
+                let has_base = match kind {
+                    SvnState::Unversioned => false,
+                    SvnState::Added => entry.copied,
+                    _ => true,
+                };
+                ToolLaunch::diff(&self.tools, &self.svn, &self.directory, &file, has_base)
+            }
+        };
+        match launch {
+            Ok(launch) => self.pending_tool = Some(launch),
+            Err(e) => self.show_modal(tr("title.tool_error"), e, ModalType::Error),
+        }
+    }
+
+    /// Reads and highlights the file in the background, big files take a while
+    fn open_preview(&mut self, file: PathBuf) {
+        if self.jobs.contains(&JobKind::Preview) {
+            return;
+        }
+        let path = self.directory.join(&file);
+        let syntax_theme = self.theme.syntax;
+        self.spawn_job(JobKind::Preview, move || JobResult::Preview {
+            preview: load_preview(&path, syntax_theme),
+            file,
+        });
+    }
+
+    /// Lines the diff viewer scrolls through, the diff or the preview
+    fn viewer_len(&self) -> usize {
+        if self.mode == AppMode::Preview {
+            self.preview.lines.len()
+        } else if self.side_by_side {
+            self.diff_rows.len()
+        } else {
+            self.diff.len()
+        }
+    }
+
+    /// Entries shown by the filter, regardless of collapsed directories
+    fn visible_entry_indices(&self) -> Vec<usize> {
+        filter_entries(&self.svn.status.entries, &self.filter)
+            .into_iter()
+            .map(|row| row.idx)
+            .collect()
+    }
+
+    /// Rows between the visual anchor and the cursor, both included
+    fn visual_range(&self) -> Option<(usize, usize)> {
+        match self.mode {
+            AppMode::Visual(anchor) => {
+                let cursor = self.block_status[0].idx_selected;
+                Some((anchor.min(cursor), anchor.max(cursor)))
+            }
+            _ => None,
+        }
+    }
+
+    fn select_visual_range(&mut self) {
+        let Some((start, end)) = self.visual_range() else {
+            return;
+        };
+        let mut indices = Vec::new();
+        for row in self
+            .visible_rows()
+            .into_iter()
+            .skip(start)
+            .take(end - start + 1)
+        {
+            match row {
+                StatusRow::Entry { idx, .. } => indices.push(idx),
+                StatusRow::Dir { descendants, .. } => indices.extend(descendants),
+            }
+        }
+        let all_selected = indices
+            .iter()
+            .all(|idx| self.svn.status.selections.contains(idx));
+        self.svn.status.set_selection(&indices, !all_selected);
+    }
+
+    fn select_matching(&mut self, input: &str) {
+        match Selector::parse(input) {
+            Ok(selector) => {
+                let indices: Vec<usize> = self
+                    .visible_entry_indices()
+                    .into_iter()
+                    .filter(|&idx| selector.matches(&self.svn.status.entries[idx]))
+                    .collect();
+                self.svn.status.set_selection(&indices, true);
+                self.mode = AppMode::Normal;
+            }
+            Err(error) => self.show_modal(
+                tr("title.invalid_pattern"),
+                trf("msg.invalid_pattern", &[&error]),
+                ModalType::Error,
+            ),
+        }
+    }
+
+    /// Status rows currently shown, after applying the filter and the tree view
+    fn visible_rows(&self) -> Vec<StatusRow> {
+        let visible = filter_entries(&self.svn.status.entries, &self.filter);
+        if self.tree_view {
+            tree_rows(&self.svn.status.entries, &visible, &self.collapsed)
+        } else {
+            flat_rows(&self.svn.status.entries, &visible)
+        }
+    }
+
+    fn current_row(&self) -> Option<StatusRow> {
+        self.visible_rows()
+            .into_iter()
+            .nth(self.block_status[0].idx_selected)
+    }
+
+    /// Index in `SvnStatusList.entries` of the row under the cursor
+    fn current_entry_idx(&self) -> Option<usize> {
+        self.current_row().and_then(|row| row.entry_idx())
+    }
+
+    fn toggle_current_selection(&mut self) {
+        match self.current_row() {
+            Some(StatusRow::Entry { idx, .. }) => self.svn.status.toggle_selection(idx),
+            Some(StatusRow::Dir { descendants, .. }) => {
+                let all_selected = descendants
+                    .iter()
+                    .all(|idx| self.svn.status.selections.contains(idx));
+                self.svn.status.set_selection(&descendants, !all_selected);
+            }
+            None => {}
+        }
+    }
+
+    fn toggle_current_collapse(&mut self) {
+        if let Some(StatusRow::Dir { path, .. }) = self.current_row()
+            && !self.collapsed.remove(&path)
+        {
+            self.collapsed.insert(path);
+        }
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.block_status[0].idx_selected = 0;
     }
 
     fn on_key_event(&mut self, key: KeyEvent) {
+        let action = self.keymap.action(self.key_context(), &key);
+        if self.show_help {
+            if let Some(Action::Close) = action {
+                self.show_help = false;
+            }
+            return;
+        }
+        if let Some(Action::Help) = action {
+            self.show_help = true;
+            return;
+        }
         match &self.mode {
-            AppMode::Normal => match (key.modifiers, key.code) {
-                (_, KeyCode::Esc | KeyCode::Char('q'))
-                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
-                (_, KeyCode::Up | KeyCode::Char('k')) => {
+            AppMode::Normal => match action {
+                Some(Action::Quit) => self.quit(),
+                Some(Action::MoveUp) => {
                     self.block_status[0].idx_selected =
                         move_cursor_up(self.block_status[0].idx_selected);
                 }
-                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                Some(Action::MoveDown) => {
                     self.block_status[0].idx_selected = move_cursor_down(
                         self.block_status[0].idx_selected,
-                        self.svn.status.entries.len(),
+                        self.visible_rows().len(),
                     );
                 }
-                (_, KeyCode::Char('y')) => {
-                    let _ = copy_file(self.block_status[0].idx_selected, &self.svn.status.entries)
-                        .expect("Error al copiar el archivo");
-                }
-                (_, KeyCode::Char('u')) => {
-                    self.svn.refresh_svn_status();
+                Some(Action::CopyPath) => {
+                    if let Some(idx) = self.current_entry_idx()
+                        && let Err(e) = copy_file(idx, &self.svn.status.entries)
+                    {
+                        self.show_modal(
+                            tr("title.clipboard_error"),
+                            trf("main.copy_failed", &[&e]),
+                            ModalType::Error,
+                        );
+                    }
                 }
-                (_, KeyCode::Char('a')) => {
-                    self.svn.add_to_svn(self.block_status[0].idx_selected);
+                Some(Action::Export) => self.export_to_clipboard(),
+                Some(Action::Refresh) => self.refresh(),
+                Some(Action::Add) => {
+                    if let Some(idx) = self.current_entry_idx() {
+                        let files: Vec<PathBuf> = self.current_file().into_iter().collect();
+                        if self.run_hook(HookStage::Pre, HookAction::Add, &files, "") {
+                            self.svn.add_to_svn(idx);
+                            self.run_hook(HookStage::Post, HookAction::Add, &files, "");
+                        }
+                    }
                 }
-                (_, KeyCode::Char('r')) => {
+                Some(Action::Revert) => {
                     self.mode = AppMode::Confirm(ConfirmMode::Revert);
                 }
-                (_, KeyCode::Char(' ')) => {
-                    self.svn
-                        .status
-                        .toggle_selection(self.block_status[0].idx_selected);
+                Some(Action::ToggleSelection) => self.toggle_current_selection(),
+                Some(Action::ToggleTree) => {
+                    self.tree_view = !self.tree_view;
+                    self.block_status[0].idx_selected = 0;
                 }
-                (_, KeyCode::Char('c')) => {
-                    self.mode = AppMode::Commit;
-                    self.block_status[2].error = false;
+                Some(Action::ToggleCollapse) => self.toggle_current_collapse(),
+                Some(Action::VisualMode) => {
+                    self.mode = AppMode::Visual(self.block_status[0].idx_selected);
+                }
+                Some(Action::SelectAll) => {
+                    let indices = self.visible_entry_indices();
+                    self.svn.status.set_selection(&indices, true);
+                }
+                Some(Action::InvertSelection) => {
+                    let indices = self.visible_entry_indices();
+                    self.svn.status.invert_selection(&indices);
+                }
+                Some(Action::ClearSelection) => self.svn.status.clear_selection(),
+                Some(Action::SelectMatching) => {
+                    self.prompt.clear();
+                    self.mode = AppMode::Prompt(PromptKind::SelectMatching);
+                }
+                Some(Action::CommitMode) => self.enter_commit_mode(),
+                Some(Action::SelectionsMode) => self.mode = AppMode::Selections,
+                Some(Action::Filter) => self.mode = AppMode::Filter,
+                Some(Action::Ignore) if self.current_entry_idx().is_some() => {
+                    self.mode = AppMode::Ignore;
+                }
+                Some(Action::ToggleUnversioned) => {
+                    self.svn.show_unversioned = !self.svn.show_unversioned;
+                    self.refresh();
+                }
+                Some(Action::ToggleIgnored) => {
+                    self.svn.show_ignored = !self.svn.show_ignored;
+                    self.refresh();
+                }
+                Some(Action::Properties) => {
+                    if let Some(file) = self.current_file() {
+                        self.open_properties(file);
+                    }
+                }
+                Some(Action::Diff) => {
+                    let Some(entry) = self
+                        .current_entry_idx()
+                        .and_then(|idx| self.svn.status.entries.get(idx))
+                    else {
+                        return;
+                    };
+                    let file = entry.file.clone();
+                    // New files have nothing to compare against
+                    if matches!(entry.state.as_str(), "?" | "A") {
+                        self.open_preview(file);
+                    } else {
+                        self.open_diff(file);
+                    }
+                }
+                Some(Action::Preview) => {
+                    if let Some(file) = self.current_file() {
+                        self.open_preview(file);
+                    }
+                }
+                Some(Action::ExternalTool) => self.prepare_external_tool(),
+                Some(Action::Compare) => {
+                    self.prompt.clear();
+                    self.mode = AppMode::Prompt(PromptKind::Compare);
+                }
+                Some(Action::Lock) => {
+                    self.prompt.clear();
+                    self.mode = AppMode::Prompt(PromptKind::LockMessage { force: false });
+                }
+                Some(Action::Unlock) => self.unlock_files(false),
+                Some(Action::ForceLock) => self.mode = AppMode::Confirm(ConfirmMode::ForceLock),
+                Some(Action::ForceUnlock) => {
+                    self.mode = AppMode::Confirm(ConfirmMode::ForceUnlock);
+                }
+                Some(Action::Update) => self.update_files(self.target_files(), false),
+                Some(Action::ToggleRemote) => {
+                    self.svn.remote = !self.svn.remote;
+                    self.refresh();
                 }
-                (_, KeyCode::Char('s')) => self.mode = AppMode::Selections,
                 _ => {}
             },
-            AppMode::Commit => match (key.modifiers, key.code) {
-                (_, KeyCode::Esc) => {
+            AppMode::Properties => match action {
+                Some(Action::Cancel) => self.mode = AppMode::Normal,
+                Some(Action::MoveUp) => self.property_idx = move_cursor_up(self.property_idx),
+                Some(Action::MoveDown) => {
+                    self.property_idx = move_cursor_down(self.property_idx, self.properties.len());
+                }
+                Some(Action::AddProperty) => {
+                    self.prompt.clear();
+                    self.mode = AppMode::Prompt(PromptKind::PropertyName);
+                }
+                Some(Action::EditProperty) => {
+                    self.pending_edit = self
+                        .properties
+                        .get(self.property_idx)
+                        .cloned()
+                        .map(EditRequest::Property);
+                }
+                Some(Action::DeleteProperty) if !self.properties.is_empty() => {
+                    self.mode = AppMode::Confirm(ConfirmMode::DeleteProperty);
+                }
+                Some(Action::Diff) => self.open_diff(self.property_file.clone()),
+                _ => {}
+            },
+            AppMode::Diff | AppMode::Preview => match action {
+                Some(Action::Close) => self.mode = std::mem::take(&mut self.diff_return),
+                Some(Action::MoveUp) => self.diff_scroll = self.diff_scroll.saturating_sub(1),
+                Some(Action::MoveDown) => {
+                    self.diff_scroll = move_cursor_down(self.diff_scroll, self.viewer_len());
+                }
+                Some(Action::PageUp) => {
+                    self.diff_scroll = self.diff_scroll.saturating_sub(self.diff_page.max(1));
+                }
+                Some(Action::PageDown) => {
+                    self.diff_scroll = (self.diff_scroll + self.diff_page.max(1))
+                        .min(self.viewer_len().saturating_sub(1));
+                }
+                // The preview has no diff to lay out or run again
+                Some(Action::ToggleSideBySide) if self.mode == AppMode::Diff => {
+                    self.side_by_side = !self.side_by_side;
+                    self.diff_scroll = 0;
+                }
+                Some(Action::IgnoreWhitespace) if self.mode == AppMode::Diff => {
+                    self.diff_options.ignore_whitespace = !self.diff_options.ignore_whitespace;
+                    self.load_diff();
+                }
+                Some(Action::IgnoreEolStyle) if self.mode == AppMode::Diff => {
+                    self.diff_options.ignore_eol_style = !self.diff_options.ignore_eol_style;
+                    self.load_diff();
+                }
+                _ => {}
+            },
+            AppMode::Ignore => match action {
+                Some(Action::Cancel) => self.mode = AppMode::Normal,
+                Some(Action::ToggleGlobal) => self.ignore_global = !self.ignore_global,
+                Some(Action::IgnoreFile) => self.ignore_current(IgnoreTarget::File),
+                Some(Action::IgnoreExtension) => self.ignore_current(IgnoreTarget::Extension),
+                Some(Action::IgnoreDirectory) => self.ignore_current(IgnoreTarget::Directory),
+                _ => {}
+            },
+            AppMode::Visual(_) => match action {
+                Some(Action::NormalMode) => self.mode = AppMode::Normal,
+                Some(Action::MoveUp) => {
+                    self.block_status[0].idx_selected =
+                        move_cursor_up(self.block_status[0].idx_selected);
+                }
+                Some(Action::MoveDown) => {
+                    self.block_status[0].idx_selected = move_cursor_down(
+                        self.block_status[0].idx_selected,
+                        self.visible_rows().len(),
+                    );
+                }
+                Some(Action::ToggleSelection) => {
+                    self.select_visual_range();
                     self.mode = AppMode::Normal;
                 }
-                (_, KeyCode::Enter) => {
-                    match self.svn.push_basic_commit() {
-                        Ok(_) => {
-                            self.block_status[2].error = false;
-                            self.svn.status.clear_commit_message();
-                            self.mode = AppMode::Normal;
-                        }
-                        Err(error_message) => {
-                            self.block_status[2].error = true;
-                            self.modal.title = " Error de Commit ".to_string();
-                            self.modal.message = error_message;
-                            self.mode = AppMode::Modal(ModalType::Error);
+                _ => {}
+            },
+            AppMode::Prompt(kind) => match action {
+                Some(Action::Cancel) => {
+                    self.mode = match kind {
+                        PromptKind::SelectMatching
+                        | PromptKind::LockMessage { .. }
+                        | PromptKind::Compare => AppMode::Normal,
+                        PromptKind::PropertyName => AppMode::Properties,
+                    };
+                }
+                Some(Action::Submit) => {
+                    let input = std::mem::take(&mut self.prompt);
+                    match kind {
+                        PromptKind::SelectMatching => self.select_matching(&input),
+                        PromptKind::LockMessage { force } => self.lock_files(&input, *force),
+                        PromptKind::Compare => self.start_compare(&input),
+                        PromptKind::PropertyName => {
+                            self.mode = AppMode::Properties;
+                            let name = input.trim().to_string();
+                            if !name.is_empty() {
+                                let value = self
+                                    .properties
+                                    .iter()
+                                    .find(|property| property.name == name)
+                                    .map(|property| property.value.clone())
+                                    .unwrap_or_default();
+                                self.pending_edit =
+                                    Some(EditRequest::Property(SvnProperty { name, value }));
+                            }
                         }
                     }
-                    self.svn.status.clear_commit_message();
                 }
-                (_, KeyCode::Backspace) => {
+                Some(Action::DeleteChar) => {
+                    self.prompt.pop();
+                }
+                _ => {
+                    if let KeyCode::Char(c) = key.code {
+                        self.prompt.push(c);
+                    }
+                }
+            },
+            AppMode::Filter => match action {
+                Some(Action::Submit) => self.mode = AppMode::Normal,
+                Some(Action::Cancel) => {
+                    self.set_filter(String::new());
+                    self.mode = AppMode::Normal;
+                }
+                Some(Action::DeleteChar) => {
+                    let mut filter = self.filter.clone();
+                    filter.pop();
+                    self.set_filter(filter);
+                }
+                Some(Action::MoveUp) => {
+                    self.block_status[0].idx_selected =
+                        move_cursor_up(self.block_status[0].idx_selected);
+                }
+                Some(Action::MoveDown) => {
+                    self.block_status[0].idx_selected = move_cursor_down(
+                        self.block_status[0].idx_selected,
+                        self.visible_rows().len(),
+                    );
+                }
+                _ => {
+                    if let KeyCode::Char(c) = key.code {
+                        let filter = format!("{}{}", self.filter, c);
+                        self.set_filter(filter);
+                    }
+                }
+            },
+            AppMode::Commit if self.issue_focus => match action {
+                Some(Action::NormalMode) => self.mode = AppMode::Normal,
+                Some(Action::Submit) => self.commit(),
+                Some(Action::SwitchField) => self.issue_focus = false,
+                Some(Action::DeleteChar) => {
+                    self.issue.pop();
+                }
+                _ => {
+                    if let KeyCode::Char(c) = key.code
+                        && self
+                            .bugtraq
+                            .as_ref()
+                            .is_some_and(|bugtraq| bugtraq.accepts(c))
+                    {
+                        self.issue.push(c);
+                    }
+                }
+            },
+            AppMode::Commit => match action {
+                Some(Action::NormalMode) => {
+                    self.mode = AppMode::Normal;
+                }
+                Some(Action::Submit) => self.commit(),
+                Some(Action::SwitchField) if self.bugtraq.is_some() => self.issue_focus = true,
+                Some(Action::Newline) => {
+                    self.history.reset();
+                    self.svn.status.push_char_to_commit_message('\n');
+                }
+                Some(Action::NextTemplate) => self.next_template(),
+                Some(Action::EditMessage) => self.pending_edit = Some(EditRequest::CommitMessage),
+                Some(Action::MoveUp) => {
+                    let current = self.svn.status.commit_message().to_string();
+                    if let Some(message) = self.history.previous(&current) {
+                        self.svn.status.set_commit_message(message.to_string());
+                    }
+                }
+                Some(Action::MoveDown) => {
+                    if let Some(message) = self.history.next() {
+                        self.svn.status.set_commit_message(message.to_string());
+                    }
+                }
+                Some(Action::LogPicker) => self.open_log_picker(),
+                Some(Action::DeleteChar) => {
+                    self.history.reset();
                     self.svn.status.pop_char_from_commit_message();
                 }
-                (_, KeyCode::Char(c)) => {
-                    self.svn.status.push_char_to_commit_message(c);
+                _ => {
+                    if let KeyCode::Char(c) = key.code {
+                        self.history.reset();
+                        self.svn.status.push_char_to_commit_message(c);
+                    }
                 }
-                _ => {}
             },
-            AppMode::Selections => match (key.modifiers, key.code) {
-                (_, KeyCode::Esc) => self.mode = AppMode::Normal,
-                (_, KeyCode::Char('q'))
-                | (KeyModifiers::CONTROL, KeyCode::Char('c') | KeyCode::Char('C')) => self.quit(),
-                (_, KeyCode::Char('c')) => {
+            AppMode::LogPicker => match action {
+                Some(Action::Cancel) => self.mode = AppMode::Commit,
+                Some(Action::MoveUp) => self.log_idx = move_cursor_up(self.log_idx),
+                Some(Action::MoveDown) => {
+                    self.log_idx = move_cursor_down(self.log_idx, self.log_entries.len());
+                }
+                Some(Action::Submit) => {
+                    if let Some(entry) = self.log_entries.get(self.log_idx) {
+                        self.history.reset();
+                        self.svn.status.set_commit_message(entry.message.clone());
+                    }
                     self.mode = AppMode::Commit;
-                    self.block_status[2].error = false;
                 }
-                (_, KeyCode::Up | KeyCode::Char('k')) => {
+                _ => {}
+            },
+            AppMode::Compare => match action {
+                Some(Action::Close) => self.mode = AppMode::Normal,
+                Some(Action::MoveUp) => self.compare_idx = move_cursor_up(self.compare_idx),
+                Some(Action::MoveDown) => {
+                    self.compare_idx = move_cursor_down(self.compare_idx, self.compare_paths.len());
+                }
+                Some(Action::Diff) => self.open_compare_diff(),
+                _ => {}
+            },
+            AppMode::Selections => match action {
+                Some(Action::NormalMode) => self.mode = AppMode::Normal,
+                Some(Action::Quit) => self.quit(),
+                Some(Action::CommitMode) => self.enter_commit_mode(),
+                Some(Action::MoveUp) => {
                     self.block_status[1].idx_selected =
                         move_cursor_up(self.block_status[1].idx_selected);
                 }
-                (_, KeyCode::Down | KeyCode::Char('j')) => {
+                Some(Action::MoveDown) => {
                     self.block_status[1].idx_selected = move_cursor_down(
                         self.block_status[1].idx_selected,
                         self.svn.status.selections.len(),
                     );
                 }
-                (_, KeyCode::Char(' ')) => {
+                Some(Action::ToggleSelection) => {
                     self.svn
                         .status
                         .toggle_selection_by_file(self.block_status[1].idx_selected);
                 }
                 _ => {}
             },
-            AppMode::Confirm(action_type) => match (key.modifiers, key.code) {
-                (_, KeyCode::Esc | KeyCode::Backspace | KeyCode::Char('n')) => {
+            AppMode::Confirm(kind) => match action {
+                Some(Action::Cancel) => self.cancel_confirm(*kind),
+                Some(Action::Confirm) => self.accept_confirm(*kind),
+                _ => {}
+            },
+            AppMode::Modal(_) => {
+                if let Some(Action::Close) = action {
                     self.mode = AppMode::Normal;
                 }
-                (_, KeyCode::Char('y')) => {
-                    match action_type {
-                        ConfirmMode::Revert => {
-                            self.svn.revert_to_svn(self.block_status[0].idx_selected);
+            }
+            AppMode::CommitSummary => match action {
+                Some(Action::Close) => self.mode = AppMode::Normal,
+                Some(Action::CopyRevision) => {
+                    if let Some(revision) = self.committed_revision {
+                        let revision = revision.to_string();
+                        match copy_text(&revision) {
+                            Ok(()) => self.show_toast(trf("msg.revision_copied", &[&revision])),
+                            Err(e) => self.show_modal(
+                                tr("title.clipboard_error"),
+                                e.to_string(),
+                                ModalType::Error,
+                            ),
                         }
                     }
-                    self.mode = AppMode::Normal;
                 }
                 _ => {}
             },
-            AppMode::Modal(_) => match (key.modifiers, key.code) {
-                (_, KeyCode::Enter | KeyCode::Esc) => {
-                    self.mode = AppMode::Normal;
+        }
+    }
+
+    fn cancel_confirm(&mut self, kind: ConfirmMode) {
+        self.mode = match kind {
+            ConfirmMode::DeleteProperty => AppMode::Properties,
+            ConfirmMode::NoIssue => {
+                self.issue_focus = true;
+                AppMode::Commit
+            }
+            _ => AppMode::Normal,
+        };
+    }
+
+    fn accept_confirm(&mut self, kind: ConfirmMode) {
+        match kind {
+            ConfirmMode::Revert => {
+                self.mode = AppMode::Normal;
+                if let Some(idx) = self.current_entry_idx() {
+                    let files: Vec<PathBuf> = self.current_file().into_iter().collect();
+                    if self.run_hook(HookStage::Pre, HookAction::Revert, &files, "") {
+                        self.svn.revert_to_svn(idx);
+                        self.run_hook(HookStage::Post, HookAction::Revert, &files, "");
+                    }
+                }
+            }
+            ConfirmMode::ForceLock => {
+                self.prompt.clear();
+                self.mode = AppMode::Prompt(PromptKind::LockMessage { force: true });
+            }
+            ConfirmMode::ForceUnlock => self.unlock_files(true),
+            ConfirmMode::CommitAnyway => self.commit_checked(),
+            ConfirmMode::NoIssue => self.run_checks(),
+            ConfirmMode::UpdateOutOfDate => {
+                let files = std::mem::take(&mut self.out_of_date);
+                self.update_files(files, true);
+            }
+            ConfirmMode::DeleteProperty => {
+                let name = self.current_property_name();
+                match self.svn.delete_property(&self.property_file, &name) {
+                    Ok(()) => self.reload_properties(),
+                    Err(e) => self.show_modal(tr("title.property_error"), e, ModalType::Error),
+                }
+            }
+        }
+    }
+
+    fn on_mouse_event(&mut self, mouse: MouseEvent) {
+        let position = Position::new(mouse.column, mouse.row);
+        match mouse.kind {
+            MouseEventKind::ScrollUp => self.on_wheel(position, false),
+            MouseEventKind::ScrollDown => self.on_wheel(position, true),
+            MouseEventKind::Down(MouseButton::Left) => self.on_click(position),
+            _ => {}
+        }
+    }
+
+    fn pane_at(&self, position: Position) -> Option<usize> {
+        self.panes.iter().position(|pane| pane.contains(position))
+    }
+
+    /// Row of a bordered list pane under the position, counting the scroll
+    fn pane_row(&self, pane: usize, position: Position) -> Option<usize> {
+        let area = self.panes.get(pane)?;
+        let top = area.y + 1;
+        (position.y >= top && position.y + 1 < area.bottom())
+            .then(|| self.block_status[pane - 1].offset + usize::from(position.y - top))
+    }
+
+    fn on_wheel(&mut self, position: Position, down: bool) {
+        let step = |idx: usize, len: usize| {
+            if down {
+                move_cursor_down(idx, len)
+            } else {
+                move_cursor_up(idx)
+            }
+        };
+        match self.mode {
+            AppMode::Diff | AppMode::Preview => {
+                self.diff_scroll = if down {
+                    (self.diff_scroll + WHEEL_LINES).min(self.viewer_len().saturating_sub(1))
+                } else {
+                    self.diff_scroll.saturating_sub(WHEEL_LINES)
+                };
+            }
+            AppMode::Properties => {
+                self.property_idx = step(self.property_idx, self.properties.len());
+            }
+            AppMode::LogPicker => self.log_idx = step(self.log_idx, self.log_entries.len()),
+            AppMode::Compare => {
+                self.compare_idx = step(self.compare_idx, self.compare_paths.len());
+            }
+            AppMode::Normal
+            | AppMode::Selections
+            | AppMode::Commit
+            | AppMode::Filter
+            | AppMode::Visual(_) => match self.pane_at(position) {
+                Some(1) => {
+                    let len = self.visible_rows().len();
+                    self.block_status[0].idx_selected =
+                        step(self.block_status[0].idx_selected, len);
+                }
+                Some(2) => {
+                    let len = self.svn.status.selections.len();
+                    self.block_status[1].idx_selected =
+                        step(self.block_status[1].idx_selected, len);
                 }
                 _ => {}
             },
+            _ => {}
+        }
+    }
+
+    /// Clicks focus the pane and move its cursor; on the state column of the
+    /// status list they also toggle the selection
+    fn on_click(&mut self, position: Position) {
+        if let AppMode::Confirm(kind) = self.mode {
+            match self.confirm_buttons {
+                Some((yes, _)) if yes.contains(position) => self.accept_confirm(kind),
+                Some((_, no)) if no.contains(position) => self.cancel_confirm(kind),
+                _ => {}
+            }
+            return;
+        }
+        if !matches!(
+            self.mode,
+            AppMode::Normal | AppMode::Selections | AppMode::Commit
+        ) {
+            return;
+        }
+        match self.pane_at(position) {
+            Some(1) => {
+                self.mode = AppMode::Normal;
+                let rows = self.visible_rows();
+                let Some((row_idx, row)) = self
+                    .pane_row(1, position)
+                    .and_then(|row_idx| Some((row_idx, rows.get(row_idx)?)))
+                else {
+                    return;
+                };
+                self.block_status[0].idx_selected = row_idx;
+                let columns = StatusColumns::for_list(&self.svn.status);
+                let x = usize::from(position.x - self.panes[1].x).saturating_sub(1);
+                if x < status_prefix_width(row, columns) {
+                    self.toggle_current_selection();
+                }
+            }
+            Some(2) => {
+                self.mode = AppMode::Selections;
+                if let Some(row) = self.pane_row(2, position)
+                    && row < self.svn.status.selections.len()
+                {
+                    self.block_status[1].idx_selected = row;
+                }
+            }
+            Some(3) if self.mode != AppMode::Commit => self.enter_commit_mode(),
+            _ => {}
+        }
+    }
+
+    fn export_to_clipboard(&mut self) {
+        let result = export_status(&self.svn.status, ExportFormat::Json)
+            .map_err(|e| e.to_string())
+            .and_then(|json| copy_text(&json).map_err(|e| e.to_string()));
+        match result {
+            Ok(_) => self.show_modal(
+                tr("title.export"),
+                tr("msg.exported").to_string(),
+                ModalType::Info,
+            ),
+            Err(error_message) => {
+                self.show_modal(tr("title.export_error"), error_message, ModalType::Error)
+            }
         }
     }
 
@@ -0,0 +1,182 @@
+use crate::i18n::trf;
+use regex::Regex;
+use serde::Deserialize;
+use std::env;
+
+const DEFAULT_SUMMARY_MAX: usize = 72;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleConfig {
+    pub pattern: String,
+    /// Shown when the message does not match
+    pub message: String,
+    /// Check only the first line instead of the whole message
+    #[serde(default)]
+    pub summary: bool,
+}
+
+/// `[commit]` section: templates, the summary ruler and validation rules
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct CommitConfig {
+    pub templates: Vec<String>,
+    /// `0` hides the ruler
+    pub summary_max: Option<usize>,
+    pub rules: Vec<RuleConfig>,
+    /// svn user whose log feeds the message picker, `$USER` by default
+    pub author: Option<String>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    regex: Regex,
+    message: String,
+    summary: bool,
+}
+
+#[derive(Debug)]
+pub struct CommitRules {
+    pub templates: Vec<String>,
+    pub summary_max: usize,
+    pub author: Option<String>,
+    rules: Vec<Rule>,
+}
+
+impl Default for CommitRules {
+    fn default() -> Self {
+        CommitRules::from_config(&CommitConfig::default()).0
+    }
+}
+
+impl CommitRules {
+    pub fn from_config(config: &CommitConfig) -> (Self, Vec<String>) {
+        let mut errors = Vec::new();
+        let rules = config
+            .rules
+            .iter()
+            .filter_map(|rule| match Regex::new(&rule.pattern) {
+                Ok(regex) => Some(Rule {
+                    regex,
+                    message: rule.message.clone(),
+                    summary: rule.summary,
+                }),
+                Err(e) => {
+                    errors.push(trf("commit.invalid_rule", &[&rule.pattern, &e]));
+                    None
+                }
+            })
+            .collect();
+        let rules = CommitRules {
+            templates: config.templates.clone(),
+            summary_max: config.summary_max.unwrap_or(DEFAULT_SUMMARY_MAX),
+            author: config
+                .author
+                .clone()
+                .or_else(|| env::var("USER").ok())
+                .or_else(|| env::var("USERNAME").ok()),
+            rules,
+        };
+        (rules, errors)
+    }
+
+    /// Messages of the rules the commit message breaks, empty when it is valid
+    pub fn validate(&self, message: &str) -> Vec<String> {
+        let summary = summary_line(message);
+        self.rules
+            .iter()
+            .filter(|rule| {
+                let text = if rule.summary { summary } else { message };
+                !rule.regex.is_match(text)
+            })
+            .map(|rule| rule.message.clone())
+            .collect()
+    }
+}
+
+pub fn summary_line(message: &str) -> &str {
+    message.lines().next().unwrap_or_default()
+}
+
+/// Byte index where the summary goes past `summary_max` characters, `None`
+/// when it fits or the ruler is off (`0`)
+pub fn summary_overflow(summary: &str, summary_max: usize) -> Option<usize> {
+    if summary_max == 0 {
+        return None;
+    }
+    summary.char_indices().nth(summary_max).map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, message: &str, summary: bool) -> RuleConfig {
+        RuleConfig {
+            pattern: pattern.to_string(),
+            message: message.to_string(),
+            summary,
+        }
+    }
+
+    fn rules(rules: Vec<RuleConfig>) -> CommitRules {
+        let (rules, errors) = CommitRules::from_config(&CommitConfig {
+            rules,
+            ..CommitConfig::default()
+        });
+        assert!(errors.is_empty(), "{:?}", errors);
+        rules
+    }
+
+    #[test]
+    fn validate_reports_broken_rules() {
+        let rules = rules(vec![
+            rule(r"^[A-Z]", "Start with a capital", true),
+            rule(r"(?m)^Refs: #\d+$", "Name an issue", false),
+        ]);
+        assert!(rules.validate("Fix the parser\n\nRefs: #12").is_empty());
+        assert_eq!(
+            rules.validate("fix the parser\n\nRefs: #12"),
+            vec!["Start with a capital"]
+        );
+        assert_eq!(rules.validate("Fix the parser"), vec!["Name an issue"]);
+    }
+
+    #[test]
+    fn summary_rules_only_see_the_first_line() {
+        let rules = rules(vec![rule(r"^\S+$", "One word summary", true)]);
+        assert!(rules.validate("Fix\nthe rest has spaces").is_empty());
+        assert_eq!(rules.validate("Fix it\n"), vec!["One word summary"]);
+    }
+
+    #[test]
+    fn invalid_rules_are_reported() {
+        let (rules, errors) = CommitRules::from_config(&CommitConfig {
+            rules: vec![rule("(", "never", false), rule("x", "Needs an x", false)],
+            ..CommitConfig::default()
+        });
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains('('), "{}", errors[0]);
+        assert_eq!(rules.validate("y"), vec!["Needs an x"]);
+    }
+
+    #[test]
+    fn summary_max_defaults_and_can_be_off() {
+        assert_eq!(CommitRules::default().summary_max, DEFAULT_SUMMARY_MAX);
+        let (rules, _) = CommitRules::from_config(&CommitConfig {
+            summary_max: Some(0),
+            ..CommitConfig::default()
+        });
+        assert_eq!(rules.summary_max, 0);
+    }
+
+    #[test]
+    fn summary_overflow_counts_characters() {
+        assert_eq!(summary_line("First\nsecond"), "First");
+        assert_eq!(summary_line(""), "");
+        assert_eq!(summary_overflow("12345", 5), None);
+        assert_eq!(summary_overflow("123456", 5), Some(5));
+        // `ñ` takes two bytes
+        assert_eq!(summary_overflow("ññññññ", 5), Some(10));
+        assert_eq!(summary_overflow("a very long summary", 0), None);
+    }
+}
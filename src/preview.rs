@@ -0,0 +1,123 @@
+use crate::i18n::trf;
+use ratatui::style::{Color, Modifier, Style};
+use std::{fs::File, io::Read, path::Path, sync::OnceLock};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{self, FontStyle, ThemeSet},
+    parsing::{SyntaxReference, SyntaxSet},
+    util::LinesWithEndings,
+};
+
+/// Bytes of the file shown; highlighting more would keep the spinner going
+pub const MAX_PREVIEW_BYTES: u64 = 512 * 1024;
+/// A NUL in the first bytes marks the file as binary, like git does
+const BINARY_CHECK_BYTES: usize = 8000;
+const TAB: &str = "    ";
+
+/// Styled pieces of one line of the file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreviewLine {
+    pub spans: Vec<(Style, String)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Preview {
+    pub lines: Vec<PreviewLine>,
+    pub size: u64,
+    /// Only the first `MAX_PREVIEW_BYTES` are shown
+    pub truncated: bool,
+    pub binary: bool,
+}
+
+fn syntaxes() -> &'static SyntaxSet {
+    static SYNTAXES: OnceLock<SyntaxSet> = OnceLock::new();
+    SYNTAXES.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn themes() -> &'static ThemeSet {
+    static THEMES: OnceLock<ThemeSet> = OnceLock::new();
+    THEMES.get_or_init(ThemeSet::load_defaults)
+}
+
+/// Reads the start of the file and highlights it with the syntect theme,
+/// or leaves it plain when there is none
+pub fn load_preview(path: &Path, syntax_theme: Option<&str>) -> Result<Preview, String> {
+    let read_failed = |e: std::io::Error| trf("preview.read_failed", &[&path.display(), &e]);
+    let file = File::open(path).map_err(read_failed)?;
+    let metadata = file.metadata().map_err(read_failed)?;
+    if metadata.is_dir() {
+        return Err(trf("preview.directory", &[&path.display()]));
+    }
+    let mut bytes = Vec::new();
+    file.take(MAX_PREVIEW_BYTES)
+        .read_to_end(&mut bytes)
+        .map_err(read_failed)?;
+    let mut preview = Preview {
+        size: metadata.len(),
+        truncated: metadata.len() > MAX_PREVIEW_BYTES,
+        ..Preview::default()
+    };
+    if bytes.iter().take(BINARY_CHECK_BYTES).any(|b| *b == 0) {
+        preview.binary = true;
+        return Ok(preview);
+    }
+    let text = String::from_utf8_lossy(&bytes);
+    preview.lines = match syntax_theme.and_then(|name| themes().themes.get(name)) {
+        Some(theme) => highlight(&text, find_syntax(path, &text), theme),
+        None => text.lines().map(plain_line).collect(),
+    };
+    Ok(preview)
+}
+
+fn find_syntax<'a>(path: &Path, text: &str) -> &'a SyntaxReference {
+    let set = syntaxes();
+    path.extension()
+        .and_then(|extension| set.find_syntax_by_extension(&extension.to_string_lossy()))
+        .or_else(|| set.find_syntax_by_first_line(text))
+        .unwrap_or_else(|| set.find_syntax_plain_text())
+}
+
+fn plain_line(line: &str) -> PreviewLine {
+    PreviewLine {
+        spans: vec![(Style::default(), line.replace('\t', TAB))],
+    }
+}
+
+fn highlight(
+    text: &str,
+    syntax: &SyntaxReference,
+    theme: &highlighting::Theme,
+) -> Vec<PreviewLine> {
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    LinesWithEndings::from(text)
+        .map(|line| match highlighter.highlight_line(line, syntaxes()) {
+            Ok(pieces) => PreviewLine {
+                spans: pieces
+                    .into_iter()
+                    .map(|(style, piece)| {
+                        let piece = piece.trim_end_matches(['\n', '\r']).replace('\t', TAB);
+                        (to_style(style), piece)
+                    })
+                    .collect(),
+            },
+            // The rest of the file keeps its text, just without colors
+            Err(_) => plain_line(line.trim_end_matches(['\n', '\r'])),
+        })
+        .collect()
+}
+
+/// Foreground and font style only, the background stays the terminal's
+fn to_style(style: highlighting::Style) -> Style {
+    let color = style.foreground;
+    let mut result = Style::new().fg(Color::Rgb(color.r, color.g, color.b));
+    for (font, modifier) in [
+        (FontStyle::BOLD, Modifier::BOLD),
+        (FontStyle::ITALIC, Modifier::ITALIC),
+        (FontStyle::UNDERLINE, Modifier::UNDERLINED),
+    ] {
+        if style.font_style.contains(font) {
+            result = result.add_modifier(modifier);
+        }
+    }
+    result
+}
@@ -1,12 +1,22 @@
-use crate::svn::{SvnStatusEntry, SvnStatusList, style_for_status};
+use crate::{
+    bugtraq::Bugtraq,
+    diff::{DiffKind, DiffLine, DiffOptions, SideCell, SideRow},
+    i18n::{tr, trf},
+    message::{summary_line, summary_overflow},
+    preview::{MAX_PREVIEW_BYTES, Preview},
+    svn::{ChangedPath, LogEntry, SvnProperty, SvnStatusEntry, SvnStatusList},
+    theme::Theme,
+    tree::StatusRow,
+};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    style::{Color, Style},
-    text::{Line, Text},
-    widgets::{Block, BorderType, Clear, List, ListItem, Paragraph, Wrap},
+    style::Style,
+    text::Line,
+    widgets::{Block, BorderType, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
+use std::time::Instant;
 
 pub fn create_layout(frame: &Frame) -> Vec<Rect> {
     let main_chunks = Layout::default()
@@ -15,6 +25,7 @@ pub fn create_layout(frame: &Frame) -> Vec<Rect> {
             Constraint::Length(3),
             Constraint::Min(10),
             Constraint::Min(7),
+            Constraint::Length(1),
         ])
         .split(frame.area());
     let horizontal_chunks = Layout::default()
@@ -26,6 +37,7 @@ pub fn create_layout(frame: &Frame) -> Vec<Rect> {
         main_chunks[1],
         horizontal_chunks[0],
         horizontal_chunks[1],
+        main_chunks[3],
     ]
 }
 
@@ -33,6 +45,8 @@ pub fn create_layout(frame: &Frame) -> Vec<Rect> {
 pub struct BlockRenderStatus {
     pub idx_selected: usize,
     pub error: bool,
+    /// First row on screen at the last draw, to map clicks to rows
+    pub offset: usize,
 }
 
 impl BlockRenderStatus {
@@ -40,20 +54,44 @@ impl BlockRenderStatus {
         BlockRenderStatus {
             idx_selected: 0,
             error: false,
+            offset: 0,
         }
     }
 }
 
 pub struct ProjectInfo {
     path: String,
+    pub remote: bool,
+    /// Lock details of the entry under the cursor
+    pub lock: Option<String>,
+    /// Spinner frame and what is running in the background
+    pub busy: Option<String>,
 }
 
 impl ProjectInfo {
     pub fn new(path: String) -> Self {
-        ProjectInfo { path }
+        ProjectInfo {
+            path,
+            remote: false,
+            lock: None,
+            busy: None,
+        }
     }
 }
 
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+pub fn spinner_frame(tick: usize) -> char {
+    SPINNER[tick % SPINNER.len()]
+}
+
+/// Short notice in the top right corner that goes away by itself
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub expires: Instant,
+}
+
 #[derive(Debug, Default, PartialEq)]
 pub struct ModalInfo {
     pub title: String,
@@ -78,40 +116,103 @@ pub enum ModalType {
     None,
 }
 
-pub fn create_section_info(info: &ProjectInfo) -> Paragraph {
-    Paragraph::new(Text::styled(
-        info.path.to_string(),
-        Style::default().fg(Color::Blue),
-    ))
-    .block(
+pub fn create_section_info<'a>(info: &'a ProjectInfo, theme: &Theme) -> Paragraph<'a> {
+    let mut spans = vec![Span::styled(info.path.as_str(), theme.info)];
+    if info.remote {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(tr("info.remote"), theme.text));
+    }
+    if let Some(lock) = &info.lock {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(lock.as_str(), theme.status_style("K")));
+    }
+    if let Some(busy) = &info.busy {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(busy.as_str(), theme.info));
+    }
+    Paragraph::new(Line::from(spans)).block(
         Block::bordered()
-            .title(" Project info ")
-            .border_style(Style::new().gray())
+            .title(tr("title.project_info"))
+            .border_style(theme.border_normal)
             .border_type(BorderType::Rounded),
     )
 }
 
-pub fn create_section_status(list: &SvnStatusList, is_error: bool, is_focused: bool) -> List {
-    let status_list: Vec<ListItem> = list
-        .entries
+/// Optional columns of the status list, shown only when some entry uses them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StatusColumns {
+    pub lock: bool,
+    pub out_of_date: bool,
+}
+
+impl StatusColumns {
+    pub fn for_list(list: &SvnStatusList) -> Self {
+        StatusColumns {
+            lock: list.entries.iter().any(|entry| entry.lock.is_some()),
+            out_of_date: list.entries.iter().any(|entry| entry.out_of_date),
+        }
+    }
+}
+
+pub fn create_section_status<'a>(
+    list: &'a SvnStatusList,
+    rows: &[StatusRow],
+    filter: &str,
+    marked: Option<(usize, usize)>,
+    theme: &Theme,
+    is_error: bool,
+    is_focused: bool,
+) -> List<'a> {
+    let columns = StatusColumns::for_list(list);
+    let status_list: Vec<ListItem> = rows
         .iter()
         .enumerate()
-        .map(|(i, _entry)| {
-            let style_spans = create_status_line_spans(i, &list);
+        .map(|(i, row)| {
+            let style_spans = match row {
+                StatusRow::Entry {
+                    idx,
+                    depth,
+                    label,
+                    positions,
+                } => create_status_line_spans(*idx, label, positions, *depth, columns, list, theme),
+                StatusRow::Dir { .. } => create_dir_line_spans(row, list, theme),
+            };
             let line = Line::from(style_spans);
-            ListItem::new(line)
+            match marked {
+                Some((start, end)) if (start..=end).contains(&i) => {
+                    ListItem::new(line).style(theme.visual)
+                }
+                _ => ListItem::new(line),
+            }
         })
         .collect();
+    let shown = rows
+        .iter()
+        .filter(|row| matches!(row, StatusRow::Entry { .. }))
+        .count();
+    let title = if filter.is_empty() {
+        tr("title.status").to_string()
+    } else {
+        trf(
+            "title.status_filtered",
+            &[&filter, &shown, &list.entries.len()],
+        )
+    };
     let mut status_block = Block::bordered()
-        .title(" Status ")
+        .title(title)
         .border_type(BorderType::Rounded);
-    status_block = set_status_block(status_block, is_error, is_focused);
+    status_block = set_status_block(status_block, theme, is_error, is_focused);
     List::new(status_list)
         .block(status_block)
-        .highlight_style(Style::new().fg(Color::White).bg(Color::DarkGray))
+        .highlight_style(theme.highlight)
 }
 
-pub fn create_selected_items(list: &SvnStatusList, is_error: bool, is_focused: bool) -> List {
+pub fn create_selected_items<'a>(
+    list: &'a SvnStatusList,
+    theme: &Theme,
+    is_error: bool,
+    is_focused: bool,
+) -> List<'a> {
     let mut selected_entries: Vec<&SvnStatusEntry> = list
         .selections
         .iter()
@@ -121,7 +222,7 @@ pub fn create_selected_items(list: &SvnStatusList, is_error: bool, is_focused: b
     let selected_items: Vec<ListItem> = selected_entries
         .into_iter()
         .map(|entry| {
-            let style = style_for_status(&entry.state);
+            let style = theme.status_style(&entry.state);
             let line = Line::from(vec![
                 Span::styled(entry.state.to_string(), style),
                 Span::raw(" "),
@@ -131,59 +232,236 @@ pub fn create_selected_items(list: &SvnStatusList, is_error: bool, is_focused: b
         })
         .collect();
     let mut selected_block = Block::bordered()
-        .title(" Selected ")
+        .title(tr("title.selected"))
         .border_style(Style::new())
         .border_type(BorderType::Rounded);
-    selected_block = set_status_block(selected_block, is_error, is_focused);
+    selected_block = set_status_block(selected_block, theme, is_error, is_focused);
     List::new(selected_items)
         .block(selected_block)
-        .highlight_style(Style::new().bg(Color::DarkGray))
+        .highlight_style(theme.highlight)
 }
 
-pub fn create_status_line_spans(idx: usize, list: &SvnStatusList) -> Vec<Span> {
+/// `matched` holds the char positions of `label` to highlight
+pub fn create_status_line_spans<'a>(
+    idx: usize,
+    label: &str,
+    matched: &[usize],
+    depth: usize,
+    columns: StatusColumns,
+    list: &'a SvnStatusList,
+    theme: &Theme,
+) -> Vec<Span<'a>> {
     if let Some(entry) = list.entries.get(idx) {
-        let base_selected = Style::new().bg(Color::Blue).fg(Color::Black);
+        let base_selected = theme.selected;
         let status_span = if list.selections.contains(&idx) {
             Span::styled(entry.state.to_string(), base_selected)
         } else {
-            Span::styled(entry.state.to_string(), style_for_status(&entry.state))
+            Span::styled(entry.state.to_string(), theme.status_style(&entry.state))
         };
-        let file_span = if list.selections.contains(&idx) {
-            Span::styled(entry.file.to_string_lossy(), base_selected)
+        let file_style = if list.selections.contains(&idx) {
+            base_selected
         } else {
-            Span::raw(entry.file.to_string_lossy())
+            Style::new()
         };
         let divider_span = if list.selections.contains(&idx) {
             Span::styled(" ", base_selected)
         } else {
             Span::raw(" ")
         };
-        vec![status_span, divider_span, file_span]
+        let mut spans = vec![Span::raw("  ".repeat(depth)), status_span];
+        if columns.lock {
+            let lock = entry.lock.unwrap_or(' ').to_string();
+            let lock_style = theme.status_style(&lock);
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(lock, lock_style));
+        }
+        if columns.out_of_date {
+            let marker = if entry.out_of_date { "*" } else { " " };
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(marker, theme.status_style(marker)));
+        }
+        spans.push(divider_span);
+        spans.extend(highlight_matches(
+            label,
+            matched,
+            file_style,
+            file_style.patch(theme.matched),
+        ));
+        spans
+    } else {
+        vec![Span::raw(trf("msg.invalid_index", &[&idx])).style(theme.error_text)]
+    }
+}
+
+/// Width of what `create_status_line_spans` or `create_dir_line_spans` draw
+/// before the name: indentation, state and the optional columns
+pub fn status_prefix_width(row: &StatusRow, columns: StatusColumns) -> usize {
+    match row {
+        StatusRow::Entry { depth, .. } => {
+            2 * depth + 1 + 2 * usize::from(columns.lock) + 2 * usize::from(columns.out_of_date)
+        }
+        StatusRow::Dir { depth, own, .. } => 2 * depth + 2 + 2 * usize::from(own.is_some()),
+    }
+}
+
+/// A directory of the tree view: fold marker, name and counts of M/A/D/?
+pub fn create_dir_line_spans<'a>(
+    row: &StatusRow,
+    list: &'a SvnStatusList,
+    theme: &Theme,
+) -> Vec<Span<'a>> {
+    let StatusRow::Dir {
+        depth,
+        label,
+        collapsed,
+        counts,
+        descendants,
+        own,
+        ..
+    } = row
+    else {
+        return Vec::new();
+    };
+    let all_selected =
+        !descendants.is_empty() && descendants.iter().all(|idx| list.selections.contains(idx));
+    let name_style = if all_selected {
+        theme.selected
     } else {
-        vec![
-            Span::raw(format!("(Error: Índice {} inválido)", idx))
-                .style(Style::new().fg(Color::Red)),
-        ]
+        theme.info
+    };
+    let marker = if *collapsed { "▸ " } else { "▾ " };
+    let mut spans = vec![
+        Span::raw("  ".repeat(*depth)),
+        Span::styled(marker, name_style),
+    ];
+    if let Some(entry) = own.and_then(|idx| list.entries.get(idx)) {
+        spans.push(Span::styled(
+            entry.state.to_string(),
+            theme.status_style(&entry.state),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled(format!("{}/", label), name_style));
+    for (state, count) in [
+        ("M", counts.modified),
+        ("A", counts.added),
+        ("D", counts.deleted),
+        ("?", counts.unversioned),
+    ] {
+        if count > 0 {
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(
+                format!("{}{}", state, count),
+                theme.status_style(state),
+            ));
+        }
+    }
+    spans
+}
+
+/// Splits `text` into spans, using `matched_style` for the chars at `matched`
+pub fn highlight_matches(
+    text: &str,
+    matched: &[usize],
+    style: Style,
+    matched_style: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+    for (i, c) in text.chars().enumerate() {
+        let is_matched = matched.contains(&i);
+        if is_matched != current_matched && !current.is_empty() {
+            let style = if current_matched {
+                matched_style
+            } else {
+                style
+            };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_matched = is_matched;
+        current.push(c);
     }
+    if !current.is_empty() {
+        let style = if current_matched {
+            matched_style
+        } else {
+            style
+        };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
+/// Issue ID typed next to the commit message, shown when the repository has
+/// `bugtraq:` properties
+pub struct IssueField<'a> {
+    pub label: &'a str,
+    pub value: &'a str,
+    pub focused: bool,
 }
 
-pub fn create_section_commit(commit_message: &str, is_error: bool, is_focused: bool) -> Paragraph {
+/// The summary line is measured against `summary_max`; the part past it is
+/// shown as an error. A `summary_max` of 0 hides the ruler.
+pub fn create_section_commit<'a>(
+    commit_message: &'a str,
+    summary_max: usize,
+    issue: Option<IssueField>,
+    theme: &Theme,
+    is_error: bool,
+    is_focused: bool,
+) -> Paragraph<'a> {
     let mut commit_block = Block::bordered()
-        .title(" Commit ")
+        .title(tr("title.commit"))
         .border_type(BorderType::Rounded);
-    commit_block = set_status_block(commit_block, is_error, is_focused);
-    Paragraph::new(commit_message.to_string())
+    commit_block = set_status_block(commit_block, theme, is_error, is_focused);
+    if let Some(issue) = issue {
+        let style = if issue.focused && is_focused {
+            theme.selected
+        } else {
+            theme.info
+        };
+        commit_block = commit_block.title(
+            Line::styled(format!(" {}: {} ", issue.label, issue.value), style).right_aligned(),
+        );
+    }
+    let mut lines: Vec<Line> = commit_message.split('\n').map(Line::raw).collect();
+    if summary_max > 0 {
+        let summary = summary_line(commit_message);
+        let length = summary.chars().count();
+        let overflow = summary_overflow(summary, summary_max);
+        if let Some(split) = overflow {
+            lines[0] = Line::from(vec![
+                Span::raw(&summary[..split]),
+                Span::styled(&summary[split..], theme.error_text),
+            ]);
+        }
+        let ruler_style = if overflow.is_some() {
+            theme.error_text
+        } else {
+            theme.info
+        };
+        commit_block = commit_block.title_bottom(
+            Line::styled(format!(" {}/{} ", length, summary_max), ruler_style).right_aligned(),
+        );
+    }
+    Paragraph::new(lines)
         .block(commit_block)
         .wrap(Wrap { trim: false })
 }
 
-pub fn set_status_block(block: Block, is_error: bool, is_focused: bool) -> Block {
+pub fn set_status_block<'a>(
+    block: Block<'a>,
+    theme: &Theme,
+    is_error: bool,
+    is_focused: bool,
+) -> Block<'a> {
     if is_error {
-        block.border_style(Style::new().red().bold())
+        block.border_style(theme.border_error)
     } else if is_focused {
-        block.border_style(Style::new().blue().bold())
+        block.border_style(theme.border_focused)
     } else {
-        block.border_style(Style::new().gray())
+        block.border_style(theme.border_normal)
     }
 }
 
@@ -215,13 +493,21 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(middle_vertical_area)[1]
 }
 
-pub fn render_confirm_modal(frame: &mut Frame, title: &str, message: &str) {
+/// `yes_label` and `no_label` name the options together with their keys
+pub fn render_confirm_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    message: &str,
+    yes_label: &str,
+    no_label: &str,
+) -> (Rect, Rect) {
     let area = centered_rect(60, 20, frame.area());
     frame.render_widget(Clear, area);
     let outer_block = Block::bordered()
         .title(title)
         .border_type(BorderType::Rounded)
-        .border_style(Style::new().blue())
+        .border_style(theme.modal_confirm)
         .title_alignment(Alignment::Center);
     let inner_area = outer_block.inner(area);
     frame.render_widget(outer_block, area);
@@ -230,44 +516,490 @@ pub fn render_confirm_modal(frame: &mut Frame, title: &str, message: &str) {
         .constraints([Constraint::Min(3), Constraint::Min(1)])
         .split(inner_area);
     let message_paraghap = Paragraph::new(message)
-        .style(Style::default().fg(Color::White))
+        .style(theme.text)
         .alignment(Alignment::Center);
     frame.render_widget(message_paraghap, modal_layout[0]);
     let option_layout = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Min(3), Constraint::Min(1)])
         .split(modal_layout[1]);
-    let yes_text = Paragraph::new("Yes (y)")
-        .style(Style::default().fg(Color::Blue))
+    let yes_text = Paragraph::new(yes_label)
+        .style(theme.yes)
         .alignment(Alignment::Center);
     frame.render_widget(yes_text, option_layout[0]);
-    let no_text = Paragraph::new("No (n)")
-        .style(Style::default().fg(Color::Red))
+    let no_text = Paragraph::new(no_label)
+        .style(theme.no)
         .alignment(Alignment::Center);
     frame.render_widget(no_text, option_layout[1]);
+    (option_layout[0], option_layout[1])
 }
 
-pub fn render_modal(frame: &mut Frame, title: &str, message: &str, modal_type: ModalType) {
-    let area = centered_rect(60, 20, frame.area());
+pub fn render_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    message: &str,
+    modal_type: ModalType,
+) {
+    // Grows with long messages such as a commit summary
+    let needed = (message.lines().count() + 2) * 100 / usize::from(frame.area().height.max(1));
+    let area = centered_rect(60, needed.clamp(20, 80) as u16, frame.area());
     frame.render_widget(Clear, area);
     let outer_block = Block::bordered()
         .title(title)
         .border_type(BorderType::Rounded)
         .title_alignment(Alignment::Center);
-    let styled_block = set_modal_status(outer_block, modal_type);
+    let styled_block = set_modal_status(outer_block, theme, modal_type);
     let modal_block = Paragraph::new(message)
         .block(styled_block)
         .wrap(Wrap { trim: false });
     frame.render_widget(modal_block, area);
 }
 
-pub fn set_modal_status(block: Block, modal_type: ModalType) -> Block {
-    let mut style = Style::default();
-    style = match modal_type {
-        ModalType::Info => style.blue(),
-        ModalType::Warning => style.yellow(),
-        ModalType::Error => style.red(),
-        _ => style,
+/// Stacks the toasts under each other from the top right corner
+pub fn render_toasts(frame: &mut Frame, theme: &Theme, toasts: &[Toast]) {
+    let area = frame.area();
+    let mut y = area.y + 1;
+    for toast in toasts {
+        let width = (toast.text.chars().count() as u16 + 4).min(area.width);
+        if y + 3 > area.bottom() {
+            break;
+        }
+        let rect = Rect::new(area.right().saturating_sub(width + 1), y, width, 3);
+        frame.render_widget(Clear, rect);
+        frame.render_widget(
+            Paragraph::new(toast.text.as_str()).style(theme.text).block(
+                Block::bordered()
+                    .border_type(BorderType::Rounded)
+                    .border_style(theme.modal_info),
+            ),
+            rect,
+        );
+        y += 3;
+    }
+}
+
+/// The text with the issue references styled as links
+fn issue_spans<'a>(text: &'a str, bugtraq: Option<&Bugtraq>, theme: &Theme) -> Vec<Span<'a>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    for issue in bugtraq
+        .map(|bugtraq| bugtraq.issue_refs(text))
+        .unwrap_or_default()
+    {
+        if issue.range.start < start {
+            continue;
+        }
+        spans.push(Span::styled(&text[start..issue.range.start], theme.text));
+        spans.push(Span::styled(&text[issue.range.clone()], theme.link));
+        start = issue.range.end;
+    }
+    spans.push(Span::styled(&text[start..], theme.text));
+    spans
+}
+
+/// Commits to pick a message from: revision, date and summary line, with the
+/// tracker links of the selected one at the bottom
+pub fn render_log_picker(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    entries: &[LogEntry],
+    selected: usize,
+    bugtraq: Option<&Bugtraq>,
+) {
+    let area = centered_rect(80, 70, frame.area());
+    frame.render_widget(Clear, area);
+    let block = Block::bordered()
+        .title(title)
+        .border_type(BorderType::Rounded)
+        .border_style(theme.modal_info)
+        .title_alignment(Alignment::Center);
+    if entries.is_empty() {
+        let empty = Paragraph::new(tr("msg.no_log"))
+            .style(theme.text)
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+    let urls: Vec<String> = match (bugtraq, entries.get(selected)) {
+        (Some(bugtraq), Some(entry)) => bugtraq
+            .issue_refs(&entry.message)
+            .iter()
+            .filter_map(|issue| bugtraq.issue_url(&issue.id))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let block = if urls.is_empty() {
+        block
+    } else {
+        block.title_bottom(Line::styled(format!(" {} ", urls.join("  ")), theme.link))
+    };
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|entry| {
+            let date: String = entry.date.chars().take(10).collect();
+            let mut spans = vec![
+                Span::styled(format!("r{}", entry.revision), theme.info),
+                Span::raw("  "),
+                Span::styled(date, theme.info),
+                Span::raw("  "),
+            ];
+            spans.extend(issue_spans(summary_line(&entry.message), bugtraq, theme));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Changed paths of a compare, with the state and property columns of `svn status`
+pub fn render_compare_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    paths: &[ChangedPath],
+    selected: usize,
+) {
+    let area = centered_rect(80, 70, frame.area());
+    frame.render_widget(Clear, area);
+    let block = Block::bordered()
+        .title(title)
+        .border_type(BorderType::Rounded)
+        .border_style(theme.modal_info)
+        .title_alignment(Alignment::Center);
+    if paths.is_empty() {
+        let empty = Paragraph::new(tr("msg.no_changes"))
+            .style(theme.text)
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+    let items: Vec<ListItem> = paths
+        .iter()
+        .map(|changed| {
+            let props = if changed.props { "M" } else { " " };
+            ListItem::new(Line::from(vec![
+                Span::styled(changed.state.as_str(), theme.status_style(&changed.state)),
+                Span::styled(props, theme.status_style("M")),
+                Span::raw("  "),
+                Span::styled(changed.path.as_str(), theme.text),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Lists the properties with their values below the name, one line per value line
+pub fn render_properties_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    properties: &[SvnProperty],
+    selected: usize,
+) {
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+    let block = Block::bordered()
+        .title(title)
+        .border_type(BorderType::Rounded)
+        .border_style(theme.modal_info)
+        .title_alignment(Alignment::Center);
+    if properties.is_empty() {
+        let empty = Paragraph::new(tr("msg.no_properties"))
+            .style(theme.text)
+            .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+    let items: Vec<ListItem> = properties
+        .iter()
+        .map(|property| {
+            let mut lines = vec![Line::styled(property.name.clone(), theme.info)];
+            lines.extend(
+                property
+                    .value
+                    .lines()
+                    .map(|line| Line::styled(format!("  {}", line), theme.text)),
+            );
+            ListItem::new(lines)
+        })
+        .collect();
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(theme.highlight);
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// What the diff viewer shows: the `svn diff` lines as they are, or in two
+/// columns
+#[derive(Debug, Clone, Copy)]
+pub enum DiffView<'a> {
+    Unified(&'a [DiffLine]),
+    SideBySide(&'a [SideRow]),
+}
+
+fn diff_line_style(theme: &Theme, kind: DiffKind) -> Style {
+    match kind {
+        DiffKind::Header => theme.diff_header,
+        DiffKind::Hunk => theme.diff_hunk,
+        DiffKind::Added => theme.diff_added,
+        DiffKind::Removed => theme.diff_removed,
+        DiffKind::Property => theme.diff_property,
+        DiffKind::Context => theme.text,
+    }
+}
+
+/// Draws the diff scrolled to `scroll` and returns how many lines fit, for paging
+pub fn render_diff_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    view: DiffView,
+    options: DiffOptions,
+    scroll: usize,
+) -> usize {
+    let area = centered_rect(90, 90, frame.area());
+    frame.render_widget(Clear, area);
+    let mut block = Block::bordered()
+        .title(title)
+        .border_type(BorderType::Rounded)
+        .border_style(theme.modal_info)
+        .title_alignment(Alignment::Center);
+    if let Some(extensions) = options.extensions() {
+        block =
+            block.title_bottom(Line::styled(format!(" -x {} ", extensions), theme.info).centered());
+    }
+    let text: Vec<Line> = match view {
+        DiffView::Unified([]) | DiffView::SideBySide([]) => {
+            vec![Line::styled(tr("msg.no_diff"), theme.text)]
+        }
+        DiffView::Unified(lines) => lines
+            .iter()
+            .map(|line| Line::styled(line.text.as_str(), diff_line_style(theme, line.kind)))
+            .collect(),
+        DiffView::SideBySide(rows) => {
+            let column = area.width.saturating_sub(3) as usize / 2;
+            side_by_side_lines(theme, rows, column)
+        }
+    };
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .scroll((scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(paragraph, area);
+    area.height.saturating_sub(2) as usize
+}
+
+/// Two columns of `column` cells each around a separator, long lines are cut
+fn side_by_side_lines<'a>(theme: &Theme, rows: &'a [SideRow], column: usize) -> Vec<Line<'a>> {
+    let numbers = rows
+        .iter()
+        .filter_map(|row| match row {
+            SideRow::Pair { left, right } => left.iter().chain(right).map(|cell| cell.number).max(),
+            SideRow::Full(_) => None,
+        })
+        .max()
+        .unwrap_or_default()
+        .to_string()
+        .len();
+    rows.iter()
+        .map(|row| match row {
+            SideRow::Full(line) => {
+                Line::styled(line.text.as_str(), diff_line_style(theme, line.kind))
+            }
+            SideRow::Pair { left, right } => {
+                let mut spans = side_cell_spans(theme, left.as_ref(), numbers, column);
+                spans.push(Span::styled("│", theme.border_normal));
+                spans.extend(side_cell_spans(theme, right.as_ref(), numbers, column));
+                Line::from(spans)
+            }
+        })
+        .collect()
+}
+
+/// Line number, then the text with the changed words picked out, padded or
+/// cut to `width`
+fn side_cell_spans<'a>(
+    theme: &Theme,
+    cell: Option<&'a SideCell>,
+    numbers: usize,
+    width: usize,
+) -> Vec<Span<'a>> {
+    let Some(cell) = cell else {
+        return vec![Span::raw(" ".repeat(width))];
+    };
+    let (style, word) = match cell.kind {
+        DiffKind::Added => (theme.diff_added, theme.diff_added_word),
+        DiffKind::Removed => (theme.diff_removed, theme.diff_removed_word),
+        _ => (theme.text, theme.text),
+    };
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    for range in &cell.changes {
+        pieces.push((style, &cell.text[start..range.start]));
+        pieces.push((word, &cell.text[range.clone()]));
+        start = range.end;
+    }
+    pieces.push((style, &cell.text[start..]));
+    let mut spans = vec![Span::styled(
+        format!("{:>numbers$} ", cell.number),
+        theme.info,
+    )];
+    let mut room = width.saturating_sub(numbers + 1);
+    for (style, piece) in pieces {
+        if room == 0 {
+            break;
+        }
+        let end = piece
+            .char_indices()
+            .nth(room)
+            .map_or(piece.len(), |(idx, _)| idx);
+        room -= piece[..end].chars().count();
+        if end > 0 {
+            spans.push(Span::styled(&piece[..end], style));
+        }
+    }
+    spans.push(Span::raw(" ".repeat(room)));
+    spans
+}
+
+/// File contents in the diff viewer's area, with line numbers
+pub fn render_preview_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    preview: &Preview,
+    scroll: usize,
+) -> usize {
+    let area = centered_rect(90, 90, frame.area());
+    frame.render_widget(Clear, area);
+    let mut block = Block::bordered()
+        .title(title)
+        .border_type(BorderType::Rounded)
+        .border_style(theme.modal_info)
+        .title_alignment(Alignment::Center);
+    if preview.truncated {
+        block = block.title_bottom(
+            Line::styled(
+                trf("preview.truncated", &[&(MAX_PREVIEW_BYTES / 1024)]),
+                theme.info,
+            )
+            .centered(),
+        );
+    }
+    let text: Vec<Line> = if preview.binary {
+        vec![Line::styled(
+            trf("preview.binary", &[&preview.size]),
+            theme.text,
+        )]
+    } else if preview.lines.is_empty() {
+        vec![Line::styled(tr("msg.empty_file"), theme.text)]
+    } else {
+        let width = preview.lines.len().to_string().len();
+        preview
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(idx, line)| {
+                let number = Span::styled(format!("{:>width$} ", idx + 1), theme.info);
+                let spans = line
+                    .spans
+                    .iter()
+                    .map(|(style, piece)| Span::styled(piece.as_str(), theme.text.patch(*style)));
+                Line::from(std::iter::once(number).chain(spans).collect::<Vec<_>>())
+            })
+            .collect()
+    };
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .scroll((scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(paragraph, area);
+    area.height.saturating_sub(2) as usize
+}
+
+pub fn set_modal_status<'a>(block: Block<'a>, theme: &Theme, modal_type: ModalType) -> Block<'a> {
+    let style = match modal_type {
+        ModalType::Info => theme.modal_info,
+        ModalType::Warning => theme.modal_warning,
+        ModalType::Error => theme.modal_error,
+        _ => Style::default(),
     };
     block.border_style(style)
 }
+
+pub fn create_hint_bar<'a>(hints: &'a [(String, &'a str)], theme: &Theme) -> Paragraph<'a> {
+    let mut spans = Vec::new();
+    for (i, (keys, description)) in hints.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled("  ", theme.border_normal));
+        }
+        spans.push(Span::styled(keys.as_str(), theme.info));
+        spans.push(Span::styled(
+            format!(" {}", description),
+            theme.border_normal,
+        ));
+    }
+    Paragraph::new(Line::from(spans))
+}
+
+pub fn render_help_modal(frame: &mut Frame, theme: &Theme, title: &str, rows: &[(String, &str)]) {
+    let area = centered_rect(60, 60, frame.area());
+    frame.render_widget(Clear, area);
+    let keys_width = rows.iter().map(|(keys, _)| keys.len()).max().unwrap_or(0);
+    let lines: Vec<Line> = rows
+        .iter()
+        .map(|(keys, description)| {
+            Line::from(vec![
+                Span::styled(format!("{:>width$}", keys, width = keys_width), theme.info),
+                Span::raw("  "),
+                Span::styled(*description, theme.text),
+            ])
+        })
+        .collect();
+    let block = Block::bordered()
+        .title(title)
+        .border_type(BorderType::Rounded)
+        .border_style(theme.modal_info)
+        .title_alignment(Alignment::Center);
+    frame.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+pub fn render_prompt_modal(
+    frame: &mut Frame,
+    theme: &Theme,
+    title: &str,
+    message: &str,
+    input: &str,
+) {
+    let area = centered_rect(60, 20, frame.area());
+    frame.render_widget(Clear, area);
+    let outer_block = Block::bordered()
+        .title(title)
+        .border_type(BorderType::Rounded)
+        .border_style(theme.modal_confirm)
+        .title_alignment(Alignment::Center);
+    let inner_area = outer_block.inner(area);
+    frame.render_widget(outer_block, area);
+    let prompt_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(3)])
+        .split(inner_area);
+    let message_paragraph = Paragraph::new(message)
+        .style(theme.text)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(message_paragraph, prompt_layout[0]);
+    let input_paragraph = Paragraph::new(format!("{}▏", input)).block(
+        Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(theme.border_focused),
+    );
+    frame.render_widget(input_paragraph, prompt_layout[1]);
+}
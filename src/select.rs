@@ -0,0 +1,89 @@
+use crate::svn::SvnStatusEntry;
+use glob::{MatchOptions, Pattern};
+
+const STATE_CODES: [&str; 10] = ["M", "A", "D", "C", "?", "!", "I", "R", "X", "~"];
+
+/// What the select-matching prompt accepts: a status letter or a glob
+#[derive(Debug, Clone)]
+pub enum Selector {
+    State(String),
+    Glob(Pattern),
+}
+
+impl Selector {
+    pub fn parse(input: &str) -> Result<Self, glob::PatternError> {
+        let input = input.trim();
+        if STATE_CODES.contains(&input) {
+            Ok(Selector::State(input.to_string()))
+        } else {
+            Pattern::new(input).map(Selector::Glob)
+        }
+    }
+
+    /// Globs without a `/` are matched against the file name only, like svn:ignore
+    pub fn matches(&self, entry: &SvnStatusEntry) -> bool {
+        match self {
+            Selector::State(state) => entry.state == *state,
+            Selector::Glob(pattern) => {
+                let options = MatchOptions {
+                    case_sensitive: true,
+                    require_literal_separator: true,
+                    require_literal_leading_dot: false,
+                };
+                if pattern.as_str().contains('/') {
+                    pattern.matches_path_with(&entry.file, options)
+                } else {
+                    entry
+                        .file
+                        .file_name()
+                        .is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), options))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn entry(state: &str, file: &str) -> SvnStatusEntry {
+        SvnStatusEntry::new(PathBuf::from(file), state.to_string())
+    }
+
+    fn matches(input: &str, state: &str, file: &str) -> bool {
+        Selector::parse(input).unwrap().matches(&entry(state, file))
+    }
+
+    #[test]
+    fn state_letters_select_by_state() {
+        assert!(matches("M", "M", "src/main.rs"));
+        assert!(matches(" ? ", "?", "notes.txt"));
+        assert!(!matches("M", "A", "M"));
+        // Not a state letter, so a glob for a file named `m`
+        assert!(matches("m", "A", "docs/m"));
+    }
+
+    #[test]
+    fn globs_without_slash_match_the_file_name() {
+        assert!(matches("*.rs", "M", "src/main.rs"));
+        assert!(matches("*.rs", "M", "main.rs"));
+        assert!(!matches("src*", "M", "src/main.rs"));
+        assert!(matches("*", "M", "src/.hidden"));
+        assert!(!matches("*.RS", "M", "src/main.rs"));
+    }
+
+    #[test]
+    fn globs_with_slash_match_the_whole_path() {
+        assert!(matches("src/*.rs", "M", "src/main.rs"));
+        assert!(!matches("src/*.rs", "M", "src/ui/draw.rs"));
+        assert!(matches("src/**/*.rs", "M", "src/ui/draw.rs"));
+        assert!(!matches("*/main.rs", "M", "main.rs"));
+    }
+
+    #[test]
+    fn invalid_globs_are_errors() {
+        assert!(Selector::parse("[").is_err());
+    }
+}
@@ -1,20 +1,77 @@
-use ratatui::style::{Color, Style};
+use crate::{
+    diff::{DiffOptions, added_lines, parse_diff},
+    i18n::{tr, trf},
+};
+use serde::Serialize;
 use std::collections::HashSet;
 use std::hash::Hash;
 use std::{
+    fs::File,
     path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SvnState {
+    Modified,
+    Added,
+    Deleted,
+    Conflicted,
+    Unversioned,
+    Missing,
+    Ignored,
+    Replaced,
+    External,
+    Obstructed,
+    Unknown,
+}
+
+impl SvnState {
+    pub fn from_code(state: &str) -> Self {
+        match state {
+            "M" => SvnState::Modified,
+            "A" => SvnState::Added,
+            "D" => SvnState::Deleted,
+            "C" => SvnState::Conflicted,
+            "?" => SvnState::Unversioned,
+            "!" => SvnState::Missing,
+            "I" => SvnState::Ignored,
+            "R" => SvnState::Replaced,
+            "X" => SvnState::External,
+            "~" => SvnState::Obstructed,
+            _ => SvnState::Unknown,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct SvnStatusEntry {
     pub file: PathBuf,
     pub state: String,
+    pub changelist: Option<String>,
+    /// Lock column of `svn status`: `K` ours, `O` someone else's, `T` stolen, `B` broken
+    pub lock: Option<char>,
+    /// A newer revision exists in the repository (`*` in `svn status -u`)
+    pub out_of_date: bool,
+    /// Added with history, by `svn copy` or `svn move` (`+` in the fourth column)
+    pub copied: bool,
 }
 
 impl SvnStatusEntry {
     pub fn new(file: PathBuf, state: String) -> Self {
-        SvnStatusEntry { file, state }
+        SvnStatusEntry {
+            file,
+            state,
+            changelist: None,
+            lock: None,
+            out_of_date: false,
+            copied: false,
+        }
+    }
+
+    pub fn kind(&self) -> SvnState {
+        SvnState::from_code(&self.state)
     }
 }
 
@@ -38,6 +95,17 @@ impl SvnStatusList {
         &self.commit_message
     }
 
+    pub fn selected_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self
+            .selections
+            .iter()
+            .filter_map(|&idx| self.entries.get(idx))
+            .map(|entry| entry.file.clone())
+            .collect();
+        files.sort();
+        files
+    }
+
     pub fn toggle_selection(&mut self, idx: usize) {
         if self.selections.contains(&idx) {
             self.selections.remove(&idx);
@@ -46,6 +114,27 @@ impl SvnStatusList {
         }
     }
 
+    /// Selects or deselects every index at once, e.g. all files under a directory
+    pub fn set_selection(&mut self, indices: &[usize], selected: bool) {
+        for idx in indices {
+            if selected {
+                self.selections.insert(*idx);
+            } else {
+                self.selections.remove(idx);
+            }
+        }
+    }
+
+    pub fn invert_selection(&mut self, indices: &[usize]) {
+        for idx in indices {
+            self.toggle_selection(*idx);
+        }
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.selections.clear();
+    }
+
     pub fn toggle_selection_by_file(&mut self, idx_selected: usize) {
         let file_to_remove = self
             .selections
@@ -53,10 +142,10 @@ impl SvnStatusList {
             .filter_map(|&idx| self.entries.get(idx))
             .nth(idx_selected)
             .map(|entry| entry.file.to_path_buf());
-        if let Some(file) = file_to_remove {
-            if let Some(idx) = self.entries.iter().position(|entry| entry.file == file) {
-                self.selections.remove(&idx);
-            }
+        if let Some(file) = file_to_remove
+            && let Some(idx) = self.entries.iter().position(|entry| entry.file == file)
+        {
+            self.selections.remove(&idx);
         }
     }
 
@@ -77,10 +166,379 @@ impl SvnStatusList {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IgnoreTarget {
+    File,
+    Extension,
+    Directory,
+}
+
+/// Directory whose ignore property changes and the pattern to add to it
+pub fn ignore_rule(file: &Path, target: IgnoreTarget) -> Option<(PathBuf, String)> {
+    let parent = file.parent().unwrap_or(Path::new("")).to_path_buf();
+    match target {
+        IgnoreTarget::File => {
+            let name = file.file_name()?.to_string_lossy().into_owned();
+            Some((parent, name))
+        }
+        IgnoreTarget::Extension => {
+            let extension = file.extension()?.to_string_lossy().into_owned();
+            Some((parent, format!("*.{}", extension)))
+        }
+        IgnoreTarget::Directory => {
+            let name = parent.file_name()?.to_string_lossy().into_owned();
+            let grandparent = parent.parent().unwrap_or(Path::new("")).to_path_buf();
+            Some((grandparent, name))
+        }
+    }
+}
+
+/// Parses one line of `svn status`, or of `svn status -u` when `remote` is set.
+/// The first seven columns are flags; the path starts at column 8, or after the
+/// out-of-date marker and the working revision with `-u`.
+pub fn parse_status_line(line: &str, remote: bool) -> Option<SvnStatusEntry> {
+    let flags: Vec<char> = line.chars().take(8).collect();
+    if flags.len() < 8 || !line.is_char_boundary(8) || !flags.iter().all(char::is_ascii) {
+        return None;
+    }
+    // Headers, `Status against revision:` and tree conflict descriptions (`>`)
+    if flags[6] == '>'
+        || flags[..7]
+            .iter()
+            .any(|c| c.is_ascii_alphabetic() && c.is_lowercase())
+    {
+        return None;
+    }
+    let rest = &line[8..];
+    let file = if remote {
+        let rest = rest.get(2..)?.trim_start();
+        match rest.split_once(' ') {
+            Some((revision, path))
+                if revision == "-" || revision.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                path.trim_start()
+            }
+            _ => rest,
+        }
+    } else {
+        rest
+    };
+    if file.is_empty() {
+        return None;
+    }
+    let state = [flags[0], flags[1], flags[6]]
+        .into_iter()
+        .find(|c| *c != ' ')
+        .unwrap_or(' ');
+    let mut entry = SvnStatusEntry::new(PathBuf::from(file), state.to_string());
+    entry.lock = Some(flags[5]).filter(|c| *c != ' ');
+    entry.out_of_date = remote && line[8..].starts_with('*');
+    entry.copied = flags[3] == '+';
+    Some(entry)
+}
+
+/// Error codes svn uses when a commit touches items that changed in the repository
+const OUT_OF_DATE_CODES: [&str; 3] = ["E155011", "E160028", "E170004"];
+
+pub fn is_out_of_date_error(message: &str) -> bool {
+    OUT_OF_DATE_CODES.iter().any(|code| message.contains(code)) || message.contains("out of date")
+}
+
+/// Warning of `svn propget` for a property the path does not have
+const PROPERTY_NOT_FOUND: &str = "W200017";
+
+/// Paths quoted in the out-of-date lines of a commit error, e.g.
+/// `svn: E155011: File '/wc/src/main.rs' is out of date`, relative to the
+/// working copy. The repository errors (E160028, E170004) quote paths like
+/// `'/trunk/src/main.rs'` instead, those are left out.
+pub fn out_of_date_paths(message: &str, working_copy: &Path) -> Vec<PathBuf> {
+    message
+        .lines()
+        .filter(|line| is_out_of_date_error(line))
+        .filter_map(|line| {
+            let (_, rest) = line.split_once('\'')?;
+            let (path, _) = rest.split_once('\'')?;
+            let full = working_copy.join(path);
+            full.symlink_metadata().ok()?;
+            let relative = full.strip_prefix(working_copy).ok()?;
+            Some(if relative.as_os_str().is_empty() {
+                PathBuf::from(".")
+            } else {
+                relative.to_path_buf()
+            })
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LockInfo {
+    pub owner: String,
+    pub comment: String,
+    pub created: String,
+}
+
+/// Reads the `<lock>` element of `svn info --xml`, if there is one
+pub fn parse_lock_info(xml: &str) -> Result<Option<LockInfo>, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    let Some(lock) = document
+        .descendants()
+        .find(|node| node.has_tag_name("lock"))
+    else {
+        return Ok(None);
+    };
+    let child_text = |name: &str| {
+        lock.children()
+            .find(|node| node.has_tag_name(name))
+            .and_then(|node| node.text())
+            .unwrap_or_default()
+            .to_string()
+    };
+    Ok(Some(LockInfo {
+        owner: child_text("owner"),
+        comment: child_text("comment"),
+        created: child_text("created"),
+    }))
+}
+
+/// The two sides of a compare: revisions of a working copy path, or two URLs
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompareTarget {
+    Revisions { range: String, path: String },
+    Urls { old: String, new: String },
+}
+
+fn is_url(text: &str) -> bool {
+    text.starts_with("^/") || text.contains("://")
+}
+
+impl CompareTarget {
+    /// `A:B` on `path`, `A:B PATH`, or `OLD-URL NEW-URL`. A single revision
+    /// is compared with HEAD.
+    pub fn parse(input: &str, path: &Path) -> Result<Self, String> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        match words.as_slice() {
+            [old, new] if is_url(old) && is_url(new) => Ok(CompareTarget::Urls {
+                old: old.to_string(),
+                new: new.to_string(),
+            }),
+            [range] | [range, _] if !is_url(range) => {
+                let path = match words.get(1) {
+                    Some(path) => path.to_string(),
+                    None => path.to_string_lossy().into_owned(),
+                };
+                let range = match range.split_once(':') {
+                    Some((old, new)) if !old.is_empty() && !new.is_empty() => range.to_string(),
+                    Some(_) => return Err(trf("compare.invalid", &[&input.trim()])),
+                    None => format!("{}:HEAD", range),
+                };
+                Ok(CompareTarget::Revisions { range, path })
+            }
+            _ => Err(trf("compare.invalid", &[&input.trim()])),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            CompareTarget::Revisions { range, path } => format!("{} -r {}", path, range),
+            CompareTarget::Urls { old, new } => format!("{} → {}", old, new),
+        }
+    }
+}
+
+/// What `svn diff` runs on
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffTarget {
+    /// Local changes of a file
+    WorkingCopy(PathBuf),
+    /// One path of a compare summary, as `ChangedPath::path` gives it
+    Compare { target: CompareTarget, path: String },
+}
+
+impl Default for DiffTarget {
+    fn default() -> Self {
+        DiffTarget::WorkingCopy(PathBuf::new())
+    }
+}
+
+/// One line of `svn diff --summarize`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedPath {
+    /// `A`, `M`, `D`, or a space when only the properties changed
+    pub state: String,
+    pub props: bool,
+    /// Working copy path or URL for revisions, relative to both URLs for URLs
+    pub path: String,
+}
+
+/// Reads the `<path>` elements of `svn diff --summarize --xml`
+pub fn parse_diff_summary(xml: &str) -> Result<Vec<ChangedPath>, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    Ok(document
+        .descendants()
+        .filter(|node| node.has_tag_name("path"))
+        .map(|node| {
+            let state = match node.attribute("item") {
+                Some("added") => "A",
+                Some("deleted") => "D",
+                Some("modified") => "M",
+                Some("replaced") => "R",
+                _ => " ",
+            };
+            ChangedPath {
+                state: state.to_string(),
+                props: node.attribute("props") == Some("modified"),
+                path: node.text().unwrap_or_default().trim().to_string(),
+            }
+        })
+        .collect())
+}
+
+/// Reads `<url>` from `svn info --xml`
+pub fn parse_info_url(xml: &str) -> Result<String, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    document
+        .descendants()
+        .find(|node| node.has_tag_name("url"))
+        .and_then(|node| node.text())
+        .map(|url| url.trim().to_string())
+        .ok_or_else(|| tr("svn.no_url").to_string())
+}
+
+/// Files svn leaves next to a file with a text conflict
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictFiles {
+    /// Common ancestor, `.rOLD` or `.merge-left.rN`
+    pub base: PathBuf,
+    /// Local version, `.mine` or `.working`
+    pub mine: PathBuf,
+    /// Incoming version, `.rNEW` or `.merge-right.rN`
+    pub theirs: PathBuf,
+}
+
+/// Reads the `<conflict>` files of `svn info --xml`. Older clients give names
+/// relative to the file's directory, newer ones absolute paths.
+pub fn parse_conflict_files(xml: &str, file: &Path) -> Result<Option<ConflictFiles>, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    let directory = file.parent().unwrap_or(Path::new(""));
+    let path = |name: &str| {
+        document
+            .descendants()
+            .find(|node| node.has_tag_name(name))
+            .and_then(|node| node.text())
+            .map(|text| directory.join(text.trim()))
+    };
+    Ok(path("prev-base-file")
+        .zip(path("prev-wc-file"))
+        .zip(path("cur-base-file"))
+        .map(|((base, mine), theirs)| ConflictFiles { base, mine, theirs }))
+}
+
+/// What `svn commit` reported
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommitSummary {
+    pub revision: Option<u64>,
+    /// Modified and replaced files
+    pub sent: Vec<PathBuf>,
+    pub added: Vec<PathBuf>,
+    pub deleted: Vec<PathBuf>,
+    /// Warnings from the repository, like the output of a failed post-commit hook
+    pub warnings: String,
+}
+
+/// Reads the `Sending`/`Adding`/`Deleting` lines and `Committed revision N.`
+/// from stdout; stderr only carries warnings when the commit succeeds
+pub fn parse_commit_output(stdout: &str, stderr: &str) -> CommitSummary {
+    let mut summary = CommitSummary {
+        warnings: stderr.trim().to_string(),
+        ..CommitSummary::default()
+    };
+    for line in stdout.lines() {
+        let Some((word, rest)) = line.split_once(' ') else {
+            continue;
+        };
+        // Binary files are listed as `Adding  (bin)  logo.png`
+        let path = PathBuf::from(rest.trim().trim_start_matches("(bin)").trim_start());
+        match word {
+            "Sending" | "Replacing" => summary.sent.push(path),
+            "Adding" => summary.added.push(path),
+            "Deleting" => summary.deleted.push(path),
+            "Committed" => {
+                summary.revision = rest
+                    .strip_prefix("revision ")
+                    .and_then(|revision| revision.trim_end_matches('.').parse().ok());
+            }
+            _ => {}
+        }
+    }
+    summary
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    pub revision: u64,
+    pub author: String,
+    pub date: String,
+    pub message: String,
+}
+
+/// Reads the output of `svn log --xml`
+pub fn parse_log(xml: &str) -> Result<Vec<LogEntry>, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    let entries = document
+        .descendants()
+        .filter(|node| node.has_tag_name("logentry"))
+        .filter_map(|node| {
+            let child_text = |name: &str| {
+                node.children()
+                    .find(|child| child.has_tag_name(name))
+                    .and_then(|child| child.text())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            Some(LogEntry {
+                revision: node.attribute("revision")?.parse().ok()?,
+                author: child_text("author"),
+                date: child_text("date"),
+                message: child_text("msg"),
+            })
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// A versioned property of a working copy item
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvnProperty {
+    pub name: String,
+    pub value: String,
+}
+
+/// Reads the output of `svn proplist -v --xml`
+pub fn parse_proplist(xml: &str) -> Result<Vec<SvnProperty>, String> {
+    let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+    let properties = document
+        .descendants()
+        .filter(|node| node.has_tag_name("property"))
+        .filter_map(|node| {
+            Some(SvnProperty {
+                name: node.attribute("name")?.to_string(),
+                value: node.text().unwrap_or_default().to_string(),
+            })
+        })
+        .collect();
+    Ok(properties)
+}
+
 #[derive(Debug)]
 pub struct SvnClient {
     working_copy: PathBuf,
     pub status: SvnStatusList,
+    pub show_unversioned: bool,
+    pub show_ignored: bool,
+    /// Runs `svn status -u`, asking the repository for locks and newer revisions
+    pub remote: bool,
+    /// Times the status was replaced, to tell a late background result apart
+    pub generation: u64,
 }
 
 impl SvnClient {
@@ -88,10 +546,29 @@ impl SvnClient {
         SvnClient {
             working_copy: working_copy.as_ref().to_path_buf(),
             status: SvnStatusList::new(Vec::new(), HashSet::new()),
+            show_unversioned: true,
+            show_ignored: false,
+            remote: false,
+            generation: 0,
+        }
+    }
+
+    /// A client with the same options and no status, to run on another thread
+    pub fn detached(&self) -> SvnClient {
+        SvnClient {
+            show_unversioned: self.show_unversioned,
+            show_ignored: self.show_ignored,
+            remote: self.remote,
+            ..SvnClient::new(&self.working_copy)
         }
     }
 
     pub fn raw_command(&self, args: &[&str]) -> Result<String, String> {
+        self.raw_command_with_stderr(args).map(|(stdout, _)| stdout)
+    }
+
+    /// Like `raw_command`, also returning what svn warned about on stderr
+    pub fn raw_command_with_stderr(&self, args: &[&str]) -> Result<(String, String), String> {
         let out = Command::new("svn")
             .args(args)
             .current_dir(&self.working_copy)
@@ -101,47 +578,69 @@ impl SvnClient {
         match out {
             Ok(o) => {
                 if o.status.success() {
-                    Ok(String::from_utf8_lossy(&o.stdout).into_owned())
-                } else {
+                    Ok((
+                        String::from_utf8_lossy(&o.stdout).into_owned(),
+                        String::from_utf8_lossy(&o.stderr).into_owned(),
+                    ))
+                } else if o.stderr.is_empty() {
                     Err(String::from_utf8_lossy(&o.stdout).into_owned())
+                } else {
+                    Err(String::from_utf8_lossy(&o.stderr).into_owned())
                 }
             }
-            Err(e) => Err(format!("Fallo al ejecutar el comando SVN: {}", e)),
-        }
-    }
-
-    pub fn svn_status(&self) -> Vec<SvnStatusEntry> {
-        let out_result = self.raw_command(&["status"]);
-        match out_result {
-            Ok(out_string) => {
-                let mut entries: Vec<SvnStatusEntry> = out_string
-                    .lines()
-                    .filter_map(|line| {
-                        let mut parts =
-                            line.splitn(2, |c: char| c.is_whitespace() && c != '\n' && c != '\r'); // Use a more robust split
-                        let state = parts.next()?.to_string();
-                        let file_str = parts.next()?.trim(); // Trim whitespace from the file path
-                        let file = PathBuf::from(file_str);
-                        Some(SvnStatusEntry::new(file, state))
-                    })
-                    .collect();
-                entries.sort_by(|a, b| a.file.cmp(&b.file));
-                entries
-            }
-            Err(e) => {
-                eprintln!("Error al obtener el estado de SVN: {}", e);
-                Vec::new()
-            }
+            Err(e) => Err(trf("svn.exec_failed", &[&e])),
+        }
+    }
+
+    pub fn svn_status(&self) -> Result<Vec<SvnStatusEntry>, String> {
+        let mut args = vec!["status"];
+        if self.show_ignored {
+            args.push("--no-ignore");
         }
+        if self.remote {
+            args.push("-u");
+        }
+        let out_string = self
+            .raw_command(&args)
+            .map_err(|e| trf("svn.status_failed", &[&e]))?;
+        let mut changelist: Option<String> = None;
+        let mut entries: Vec<SvnStatusEntry> = out_string
+            .lines()
+            .filter_map(|line| {
+                // Entries after this header belong to the named changelist
+                if let Some(name) = line
+                    .strip_prefix("--- Changelist '")
+                    .and_then(|rest| rest.strip_suffix("':"))
+                {
+                    changelist = Some(name.to_string());
+                    return None;
+                }
+                let mut entry = parse_status_line(line, self.remote)?;
+                entry.changelist = changelist.clone();
+                Some(entry)
+            })
+            .filter(|entry: &SvnStatusEntry| self.show_unversioned || entry.state != "?")
+            .collect();
+        entries.sort_by(|a, b| a.file.cmp(&b.file));
+        Ok(entries)
     }
 
-    pub fn init_svn_status(&mut self) {
-        let entries = self.svn_status();
+    pub fn init_svn_status(&mut self) -> Result<(), String> {
+        let entries = self.svn_status()?;
         self.status = SvnStatusList::new(entries, HashSet::new());
+        Ok(())
     }
 
+    /// Reloads the status after a command; a failure keeps the last entries
     pub fn refresh_svn_status(&mut self) {
-        let new_entries = self.svn_status();
+        if let Ok(entries) = self.svn_status() {
+            self.set_entries(entries);
+        }
+    }
+
+    /// Replaces the status, keeping the selected files selected
+    pub fn set_entries(&mut self, new_entries: Vec<SvnStatusEntry>) {
+        self.generation += 1;
         let previously_selected_files: HashSet<PathBuf> = self
             .status
             .selections
@@ -161,13 +660,13 @@ impl SvnClient {
         self.status.set_commit_message(current_commit_message);
     }
 
-    pub fn push_basic_commit(&mut self) -> Result<bool, String> {
-        let mut args = vec!["commit", "-m", self.status.commit_message()];
-        if self.status.commit_message().trim().is_empty() {
-            return Err("El mensaje de commit no puede estar vacío.".to_string());
+    pub fn push_basic_commit(&mut self, message: &str) -> Result<CommitSummary, String> {
+        let mut args = vec!["commit", "-m", message];
+        if message.trim().is_empty() {
+            return Err(tr("svn.empty_message").to_string());
         }
         if self.status.selections.is_empty() {
-            return Err("No se han seleccionado archivos para el commit.".to_string());
+            return Err(tr("svn.no_selection").to_string());
         }
         let file_args: Vec<&str> = self
             .status
@@ -177,31 +676,305 @@ impl SvnClient {
             .filter_map(|entry| entry.file.to_str())
             .collect();
         args.extend(file_args);
-        let command_result = self.raw_command(&args);
+        let command_result = self.raw_command_with_stderr(&args);
         self.refresh_svn_status();
-        match command_result {
-            Ok(_) => Ok(true),
-            Err(e) => Err(format!("Error en el commit: {}", e)),
-        }
+        command_result
+            .map(|(stdout, stderr)| parse_commit_output(&stdout, &stderr))
+            .map_err(|e| trf("svn.commit_failed", &[&e]))
     }
 
     pub fn add_to_svn(&mut self, idx: usize) {
         let mut args = vec!["add"];
-        if let Some(entry) = self.status.entries.get(idx) {
-            if let Some(file) = entry.file.to_str() {
-                args.push(file);
-            }
+        if let Some(entry) = self.status.entries.get(idx)
+            && let Some(file) = entry.file.to_str()
+        {
+            args.push(file);
         }
         let _ = self.raw_command(&args);
         self.refresh_svn_status();
     }
 
+    /// Adds a pattern for the entry to `svn:ignore` (or `svn:global-ignores`)
+    /// of the right directory. Returns the pattern and the directory used.
+    pub fn add_to_ignore(
+        &mut self,
+        idx: usize,
+        target: IgnoreTarget,
+        global: bool,
+    ) -> Result<(String, PathBuf), String> {
+        let entry = self
+            .status
+            .entries
+            .get(idx)
+            .ok_or_else(|| trf("msg.invalid_index", &[&idx]))?;
+        let (dir, pattern) =
+            ignore_rule(&entry.file, target).ok_or_else(|| tr("svn.ignore_no_rule").to_string())?;
+        let property = if global {
+            "svn:global-ignores"
+        } else {
+            "svn:ignore"
+        };
+        let dir_arg = if dir.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            dir.to_string_lossy().into_owned()
+        };
+        // A missing property just means no patterns yet. Any other failure must
+        // stop here, or the propset below would drop the existing patterns.
+        let current = match self.raw_command(&["propget", "--", property, &dir_arg]) {
+            Ok(current) => current,
+            Err(e) if e.contains(PROPERTY_NOT_FOUND) => String::new(),
+            Err(e) => return Err(trf("svn.ignore_failed", &[&e])),
+        };
+        let mut patterns: Vec<&str> = current.lines().filter(|l| !l.trim().is_empty()).collect();
+        if !patterns.contains(&pattern.as_str()) {
+            patterns.push(&pattern);
+            let value = patterns.join("\n");
+            self.raw_command(&["propset", "--", property, &value, &dir_arg])
+                .map_err(|e| trf("svn.ignore_failed", &[&e]))?;
+        }
+        self.refresh_svn_status();
+        Ok((pattern, PathBuf::from(dir_arg)))
+    }
+
+    pub fn properties(&self, file: &Path) -> Result<Vec<SvnProperty>, String> {
+        let path = file.to_string_lossy();
+        let xml = self
+            .raw_command(&["proplist", "-v", "--xml", &path])
+            .map_err(|e| trf("svn.proplist_failed", &[&e]))?;
+        parse_proplist(&xml).map_err(|e| trf("svn.proplist_failed", &[&e]))
+    }
+
+    /// Properties of the path and the ones it inherits from its parents
+    /// (svn 1.8+), the path's own last
+    pub fn inherited_properties(&self, file: &Path) -> Result<Vec<SvnProperty>, String> {
+        let path = file.to_string_lossy();
+        let xml = self
+            .raw_command(&["proplist", "-v", "--xml", "--show-inherited-props", &path])
+            .map_err(|e| trf("svn.proplist_failed", &[&e]))?;
+        parse_proplist(&xml).map_err(|e| trf("svn.proplist_failed", &[&e]))
+    }
+
+    /// `--` keeps values such as `-1` from being read as options
+    pub fn set_property(&mut self, file: &Path, name: &str, value: &str) -> Result<(), String> {
+        let path = file.to_string_lossy();
+        self.raw_command(&["propset", "--", name, value, &path])
+            .map_err(|e| trf("svn.propset_failed", &[&name, &e]))?;
+        self.refresh_svn_status();
+        Ok(())
+    }
+
+    pub fn delete_property(&mut self, file: &Path, name: &str) -> Result<(), String> {
+        let path = file.to_string_lossy();
+        self.raw_command(&["propdel", "--", name, &path])
+            .map_err(|e| trf("svn.propdel_failed", &[&name, &e]))?;
+        self.refresh_svn_status();
+        Ok(())
+    }
+
+    /// Local changes of the entry, property changes included
+    pub fn diff(&self, target: &DiffTarget, options: DiffOptions) -> Result<String, String> {
+        let mut args = vec!["diff".to_string()];
+        if let Some(extensions) = options.extensions() {
+            args.extend(["-x".to_string(), extensions]);
+        }
+        match target {
+            DiffTarget::WorkingCopy(file) => {
+                args.extend(["--".to_string(), file.to_string_lossy().into_owned()]);
+            }
+            DiffTarget::Compare {
+                target: CompareTarget::Revisions { range, .. },
+                path,
+            } => args.extend([
+                "-r".to_string(),
+                range.clone(),
+                "--".to_string(),
+                path.clone(),
+            ]),
+            DiffTarget::Compare {
+                target: CompareTarget::Urls { old, new },
+                path,
+            } => {
+                args.extend([format!("--old={}", old), format!("--new={}", new)]);
+                // The URLs themselves changed, not a path below them
+                if path != "." {
+                    args.extend(["--".to_string(), path.clone()]);
+                }
+            }
+        }
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        self.raw_command(&args)
+            .map_err(|e| trf("svn.diff_failed", &[&e]))
+    }
+
+    /// Paths that differ between the two sides, without their diffs. For
+    /// URLs the paths are made relative so the same one works on both sides.
+    pub fn summarize(&self, target: &CompareTarget) -> Result<Vec<ChangedPath>, String> {
+        let xml = match target {
+            CompareTarget::Revisions { range, path } => {
+                self.raw_command(&["diff", "--summarize", "--xml", "-r", range, "--", path])
+            }
+            CompareTarget::Urls { old, new } => {
+                self.raw_command(&["diff", "--summarize", "--xml", old, new])
+            }
+        }
+        .map_err(|e| trf("svn.summarize_failed", &[&e]))?;
+        let mut paths = parse_diff_summary(&xml).map_err(|e| trf("svn.summarize_failed", &[&e]))?;
+        if let CompareTarget::Urls { old, .. } = target {
+            // svn prints the old side's full URL, even for `^/` targets
+            let root = self.url(old)?;
+            for changed in &mut paths {
+                if let Some(relative) = changed.path.strip_prefix(&root) {
+                    let relative = relative.trim_start_matches('/');
+                    changed.path = if relative.is_empty() {
+                        ".".to_string()
+                    } else {
+                        relative.to_string()
+                    };
+                }
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Full URL of a path or of a `^/` URL
+    pub fn url(&self, target: &str) -> Result<String, String> {
+        let xml = self
+            .raw_command(&["info", "--xml", "--", target])
+            .map_err(|e| trf("svn.url_failed", &[&target, &e]))?;
+        parse_info_url(&xml).map_err(|e| trf("svn.url_failed", &[&target, &e]))
+    }
+
+    pub fn update(&mut self, files: &[PathBuf]) -> Result<(), String> {
+        let mut args = vec!["update", "--"];
+        let paths: Vec<String> = files
+            .iter()
+            .map(|file| file.to_string_lossy().into_owned())
+            .collect();
+        args.extend(paths.iter().map(String::as_str));
+        let result = self.raw_command(&args);
+        self.refresh_svn_status();
+        result
+            .map(|_| ())
+            .map_err(|e| trf("svn.update_failed", &[&e]))
+    }
+
+    /// Recent commits of `author` in the repository, newest first. `--search`
+    /// also matches messages, so the author is compared again afterwards.
+    pub fn log_by_author(&self, author: &str, limit: usize) -> Result<Vec<LogEntry>, String> {
+        let limit = limit.to_string();
+        let xml = self
+            .raw_command(&[
+                "log", "--xml", "-r", "HEAD:1", "-l", &limit, "--search", author,
+            ])
+            .map_err(|e| trf("svn.log_failed", &[&e]))?;
+        let entries = parse_log(&xml).map_err(|e| trf("svn.log_failed", &[&e]))?;
+        Ok(entries
+            .into_iter()
+            .filter(|entry| entry.author == author)
+            .collect())
+    }
+
+    /// Locks held on the file. Other users' locks are only known to the repository.
+    pub fn lock_info(&self, file: &Path, remote: bool) -> Result<Option<LockInfo>, String> {
+        let path = file.to_string_lossy();
+        let mut args = vec!["info", "--xml"];
+        if remote {
+            args.extend(["-r", "HEAD"]);
+        }
+        args.push(&path);
+        let xml = self
+            .raw_command(&args)
+            .map_err(|e| trf("svn.info_failed", &[&e]))?;
+        parse_lock_info(&xml).map_err(|e| trf("svn.info_failed", &[&e]))
+    }
+
+    /// `None` for files without a text conflict, tree conflicts leave no files
+    pub fn conflict_files(&self, file: &Path) -> Result<Option<ConflictFiles>, String> {
+        let path = file.to_string_lossy();
+        let xml = self
+            .raw_command(&["info", "--xml", "--", &path])
+            .map_err(|e| trf("svn.conflict_info_failed", &[&e]))?;
+        parse_conflict_files(&xml, file).map_err(|e| trf("svn.conflict_info_failed", &[&e]))
+    }
+
+    /// Writes the pristine copy of the file, as of the last update, to `dest`.
+    /// Goes straight to the file so binary contents stay intact.
+    pub fn cat_base(&self, file: &Path, dest: &Path) -> Result<(), String> {
+        let output = File::create(dest).map_err(|e| trf("svn.cat_failed", &[&e]))?;
+        let result = Command::new("svn")
+            .args(["cat", "-r", "BASE", "--"])
+            .arg(file)
+            .current_dir(&self.working_copy)
+            .stdout(output)
+            .stderr(Stdio::piped())
+            .output();
+        match result {
+            Ok(o) if o.status.success() => Ok(()),
+            Ok(o) => Err(trf(
+                "svn.cat_failed",
+                &[&String::from_utf8_lossy(&o.stderr).trim_end()],
+            )),
+            Err(e) => Err(trf("svn.cat_failed", &[&trf("svn.exec_failed", &[&e])])),
+        }
+    }
+
+    /// Line numbers the entry's commit would add, `None` when the whole file
+    /// is new or the diff cannot be read
+    pub fn added_lines(&self, entry: &SvnStatusEntry) -> Option<HashSet<usize>> {
+        // Copies are diffed against their source
+        if entry.state != "M" && !(entry.state == "A" && entry.copied) {
+            return None;
+        }
+        let path = entry.file.to_string_lossy();
+        let diff = self
+            .raw_command(&["diff", "--ignore-properties", "--", &path])
+            .ok()?;
+        Some(added_lines(&parse_diff(&diff)))
+    }
+
+    pub fn lock(&mut self, files: &[PathBuf], message: &str, force: bool) -> Result<(), String> {
+        let mut args = vec!["lock", "-m", message];
+        if force {
+            args.push("--force");
+        }
+        args.push("--");
+        let paths: Vec<String> = files
+            .iter()
+            .map(|file| file.to_string_lossy().into_owned())
+            .collect();
+        args.extend(paths.iter().map(String::as_str));
+        let result = self.raw_command(&args);
+        self.refresh_svn_status();
+        result
+            .map(|_| ())
+            .map_err(|e| trf("svn.lock_failed", &[&e]))
+    }
+
+    pub fn unlock(&mut self, files: &[PathBuf], force: bool) -> Result<(), String> {
+        let mut args = vec!["unlock"];
+        if force {
+            args.push("--force");
+        }
+        args.push("--");
+        let paths: Vec<String> = files
+            .iter()
+            .map(|file| file.to_string_lossy().into_owned())
+            .collect();
+        args.extend(paths.iter().map(String::as_str));
+        let result = self.raw_command(&args);
+        self.refresh_svn_status();
+        result
+            .map(|_| ())
+            .map_err(|e| trf("svn.unlock_failed", &[&e]))
+    }
+
     pub fn revert_to_svn(&mut self, idx: usize) {
         let mut args = vec!["revert"];
-        if let Some(entry) = self.status.entries.get(idx) {
-            if let Some(file) = entry.file.to_str() {
-                args.push(file);
-            }
+        if let Some(entry) = self.status.entries.get(idx)
+            && let Some(file) = entry.file.to_str()
+        {
+            args.push(file);
         }
         let _ = self.raw_command(&args);
         self.refresh_svn_status();
@@ -214,18 +987,211 @@ impl Default for SvnClient {
     }
 }
 
-pub fn style_for_status(state: &str) -> Style {
-    match state {
-        "M" => Style::new().fg(Color::Blue),         // Modified
-        "A" => Style::new().fg(Color::Green),        // Added
-        "D" => Style::new().fg(Color::Red),          // Deleted
-        "C" => Style::new().fg(Color::LightRed),     // Conflict
-        "?" => Style::new().fg(Color::Yellow),       // Untracked
-        "!" => Style::new().fg(Color::LightRed),     // Missing
-        "I" => Style::new().fg(Color::DarkGray),     // Ignored
-        "R" => Style::new().fg(Color::Cyan),         // Replaced
-        "X" => Style::new().fg(Color::Magenta),      // External
-        "~" => Style::new().fg(Color::LightMagenta), // Obstructed
-        _ => Style::new(),                           // Default
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn working_copy() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+    }
+
+    #[test]
+    fn out_of_date_paths_keeps_working_copy_paths() {
+        let message = format!(
+            "svn: E155011: Commit failed (details follow):\nsvn: E155011: File '{}' is out of date",
+            working_copy().join("src/main.rs").display()
+        );
+        assert_eq!(
+            out_of_date_paths(&message, &working_copy()),
+            vec![PathBuf::from("src/main.rs")]
+        );
+    }
+
+    #[test]
+    fn out_of_date_paths_skips_repository_paths() {
+        let message = "svn: E160028: Commit failed (details follow):\n\
+                       svn: E160028: File '/trunk/src/main.rs' is out of date";
+        assert!(out_of_date_paths(message, &working_copy()).is_empty());
+    }
+
+    #[test]
+    fn parse_status_line_reads_flags_and_path() {
+        let entry = parse_status_line("M       src/main.rs", false).unwrap();
+        assert_eq!(entry.file, PathBuf::from("src/main.rs"));
+        assert_eq!(entry.state, "M");
+        assert_eq!(entry.lock, None);
+
+        let entry = parse_status_line(" M      src/copy of lib.rs", false).unwrap();
+        assert_eq!(entry.file, PathBuf::from("src/copy of lib.rs"));
+        assert_eq!(entry.state, "M");
+
+        let entry = parse_status_line("M    K  src/locked.rs", false).unwrap();
+        assert_eq!(entry.lock, Some('K'));
+    }
+
+    #[test]
+    fn parse_status_line_reads_remote_columns() {
+        let entry = parse_status_line("M    O          42   src/main.rs", true).unwrap();
+        assert_eq!(entry.file, PathBuf::from("src/main.rs"));
+        assert_eq!(entry.lock, Some('O'));
+
+        let entry = parse_status_line("?                   notes.txt", true).unwrap();
+        assert_eq!(entry.file, PathBuf::from("notes.txt"));
+
+        let entry = parse_status_line("A                -   new.rs", true).unwrap();
+        assert_eq!(entry.file, PathBuf::from("new.rs"));
+    }
+
+    #[test]
+    fn parse_status_line_skips_other_lines() {
+        assert!(parse_status_line("Status against revision:     42", true).is_none());
+        assert!(parse_status_line("      >   local edit, incoming delete", false).is_none());
+        assert!(parse_status_line("--- Changelist 'docs':", false).is_none());
+        assert!(parse_status_line("M", false).is_none());
+    }
+
+    #[test]
+    fn parse_status_line_marks_out_of_date() {
+        let entry = parse_status_line("M    K  *       42   src/main.rs", true).unwrap();
+        assert_eq!(entry.file, PathBuf::from("src/main.rs"));
+        assert!(entry.out_of_date);
+
+        let entry = parse_status_line("        *        -   new.rs", true).unwrap();
+        assert_eq!(entry.file, PathBuf::from("new.rs"));
+        assert!(entry.out_of_date);
+
+        assert!(
+            !parse_status_line("M               42   src/main.rs", true)
+                .unwrap()
+                .out_of_date
+        );
+        assert!(!parse_status_line("M       *", false).unwrap().out_of_date);
+    }
+
+    #[test]
+    fn parse_commit_output_reads_files_and_revision() {
+        let stdout = "Sending        src/main.rs\n\
+                      Adding  (bin)  logo.png\n\
+                      Deleting       old.rs\n\
+                      Replacing      lib.rs\n\
+                      Transmitting file data ...done\n\
+                      Committing transaction...\n\
+                      Committed revision 43.\n";
+        let summary = parse_commit_output(stdout, "  warning: post-commit hook failed\n");
+        assert_eq!(summary.revision, Some(43));
+        assert_eq!(
+            summary.sent,
+            vec![PathBuf::from("src/main.rs"), PathBuf::from("lib.rs")]
+        );
+        assert_eq!(summary.added, vec![PathBuf::from("logo.png")]);
+        assert_eq!(summary.deleted, vec![PathBuf::from("old.rs")]);
+        assert_eq!(summary.warnings, "warning: post-commit hook failed");
+    }
+
+    fn conflict_xml(base: &str, mine: &str, theirs: &str) -> String {
+        format!(
+            "<info><entry><conflict>\
+             <prev-base-file>{}</prev-base-file>\
+             <prev-wc-file>{}</prev-wc-file>\
+             <cur-base-file>{}</cur-base-file>\
+             </conflict></entry></info>",
+            base, mine, theirs
+        )
+    }
+
+    #[test]
+    fn parse_conflict_files_joins_relative_names() {
+        let xml = conflict_xml("main.rs.r1", "main.rs.mine", "main.rs.r2");
+        assert_eq!(
+            parse_conflict_files(&xml, Path::new("src/main.rs")),
+            Ok(Some(ConflictFiles {
+                base: PathBuf::from("src/main.rs.r1"),
+                mine: PathBuf::from("src/main.rs.mine"),
+                theirs: PathBuf::from("src/main.rs.r2"),
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_conflict_files_keeps_absolute_paths() {
+        let xml = conflict_xml("/wc/a.txt.r1", "/wc/a.txt.mine", "/wc/a.txt.r2");
+        let files = parse_conflict_files(&xml, Path::new("a.txt"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(files.base, PathBuf::from("/wc/a.txt.r1"));
+        assert_eq!(files.mine, PathBuf::from("/wc/a.txt.mine"));
+        assert_eq!(files.theirs, PathBuf::from("/wc/a.txt.r2"));
+        assert_eq!(
+            parse_conflict_files("<info><entry/></info>", Path::new("a.txt")),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn parse_diff_summary_reads_paths() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<diff>
+<paths>
+<path item="modified" props="none" kind="file">src/main.rs</path>
+<path item="added" props="none" kind="file">src/new.rs</path>
+<path item="none" props="modified" kind="dir">src</path>
+</paths>
+</diff>"#;
+        let paths = parse_diff_summary(xml).unwrap();
+        let summary: Vec<(&str, bool, &str)> = paths
+            .iter()
+            .map(|p| (p.state.as_str(), p.props, p.path.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("M", false, "src/main.rs"),
+                ("A", false, "src/new.rs"),
+                (" ", true, "src")
+            ]
+        );
+        assert!(parse_diff_summary("not xml").is_err());
+    }
+
+    #[test]
+    fn compare_target_parses_ranges_and_urls() {
+        let path = Path::new("src/main.rs");
+        assert_eq!(
+            CompareTarget::parse("10:20", path),
+            Ok(CompareTarget::Revisions {
+                range: "10:20".to_string(),
+                path: "src/main.rs".to_string()
+            })
+        );
+        assert_eq!(
+            CompareTarget::parse(" 10 src ", path),
+            Ok(CompareTarget::Revisions {
+                range: "10:HEAD".to_string(),
+                path: "src".to_string()
+            })
+        );
+        assert_eq!(
+            CompareTarget::parse("^/trunk ^/branches/dev", path),
+            Ok(CompareTarget::Urls {
+                old: "^/trunk".to_string(),
+                new: "^/branches/dev".to_string()
+            })
+        );
+        assert!(CompareTarget::parse("10:", path).is_err());
+        assert!(CompareTarget::parse("^/trunk", path).is_err());
+        assert!(CompareTarget::parse("", path).is_err());
+    }
+
+    #[test]
+    fn parse_status_line_marks_copied_entries() {
+        let entry = parse_status_line("A  +    src/copy of main.rs", false).unwrap();
+        assert_eq!(entry.file, PathBuf::from("src/copy of main.rs"));
+        assert_eq!(entry.state, "A");
+        assert!(entry.copied);
+        assert!(
+            !parse_status_line("A       src/new.rs", false)
+                .unwrap()
+                .copied
+        );
     }
 }
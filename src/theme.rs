@@ -0,0 +1,347 @@
+use crate::i18n::trf;
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::Deserialize;
+use std::{collections::HashMap, env, str::FromStr};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub name: Option<String>,
+    pub status: HashMap<String, String>,
+    pub border: HashMap<String, String>,
+    pub modal: HashMap<String, String>,
+    pub ui: HashMap<String, String>,
+    pub diff: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub status: HashMap<String, Style>,
+    pub selected: Style,
+    pub highlight: Style,
+    pub matched: Style,
+    pub visual: Style,
+    pub info: Style,
+    pub text: Style,
+    pub error_text: Style,
+    pub yes: Style,
+    pub no: Style,
+    pub border_normal: Style,
+    pub border_focused: Style,
+    pub border_error: Style,
+    pub modal_info: Style,
+    pub modal_warning: Style,
+    pub modal_error: Style,
+    pub modal_confirm: Style,
+    pub diff_added: Style,
+    pub diff_removed: Style,
+    pub diff_hunk: Style,
+    pub diff_header: Style,
+    pub diff_property: Style,
+    /// Words that changed inside a modified line, in the side-by-side diff
+    pub diff_added_word: Style,
+    pub diff_removed_word: Style,
+    /// Issue references in log messages
+    pub link: Style,
+    /// syntect theme of the file preview, `None` leaves it uncolored
+    pub syntax: Option<&'static str>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Theme {
+            status: status_styles(&[
+                ("M", Color::Blue),
+                ("A", Color::Green),
+                ("D", Color::Red),
+                ("C", Color::LightRed),
+                ("?", Color::Yellow),
+                ("!", Color::LightRed),
+                ("I", Color::DarkGray),
+                ("R", Color::Cyan),
+                ("X", Color::Magenta),
+                ("~", Color::LightMagenta),
+                ("K", Color::Green),
+                ("O", Color::LightRed),
+                ("T", Color::Red),
+                ("B", Color::DarkGray),
+                ("*", Color::Yellow),
+            ]),
+            selected: Style::new().bg(Color::Blue).fg(Color::Black),
+            highlight: Style::new().fg(Color::White).bg(Color::DarkGray),
+            matched: Style::new().fg(Color::Yellow).bold(),
+            visual: Style::new().bg(Color::Indexed(237)),
+            info: Style::new().fg(Color::Blue),
+            text: Style::new().fg(Color::White),
+            error_text: Style::new().fg(Color::Red),
+            yes: Style::new().fg(Color::Blue),
+            no: Style::new().fg(Color::Red),
+            border_normal: Style::new().gray(),
+            border_focused: Style::new().blue().bold(),
+            border_error: Style::new().red().bold(),
+            modal_info: Style::new().blue(),
+            modal_warning: Style::new().yellow(),
+            modal_error: Style::new().red(),
+            modal_confirm: Style::new().blue(),
+            diff_added: Style::new().fg(Color::Green),
+            diff_removed: Style::new().fg(Color::Red),
+            diff_hunk: Style::new().fg(Color::Cyan),
+            diff_header: Style::new().fg(Color::White).bold(),
+            diff_property: Style::new().fg(Color::Magenta),
+            diff_added_word: Style::new().fg(Color::Black).bg(Color::Green),
+            diff_removed_word: Style::new().fg(Color::Black).bg(Color::Red),
+            link: Style::new().fg(Color::Cyan).underlined(),
+            syntax: Some("base16-ocean.dark"),
+        }
+    }
+
+    pub fn light() -> Self {
+        Theme {
+            status: status_styles(&[
+                ("M", Color::Blue),
+                ("A", Color::Green),
+                ("D", Color::Red),
+                ("C", Color::Red),
+                ("?", Color::Magenta),
+                ("!", Color::Red),
+                ("I", Color::Gray),
+                ("R", Color::Cyan),
+                ("X", Color::Magenta),
+                ("~", Color::Magenta),
+                ("K", Color::Green),
+                ("O", Color::Red),
+                ("T", Color::Red),
+                ("B", Color::Gray),
+                ("*", Color::Magenta),
+            ]),
+            selected: Style::new().bg(Color::Blue).fg(Color::White),
+            highlight: Style::new().fg(Color::Black).bg(Color::Gray),
+            matched: Style::new().fg(Color::Red).bold(),
+            visual: Style::new().bg(Color::Indexed(253)),
+            info: Style::new().fg(Color::Blue),
+            text: Style::new().fg(Color::Black),
+            error_text: Style::new().fg(Color::Red),
+            yes: Style::new().fg(Color::Blue),
+            no: Style::new().fg(Color::Red),
+            border_normal: Style::new().fg(Color::DarkGray),
+            border_focused: Style::new().fg(Color::Blue).bold(),
+            border_error: Style::new().fg(Color::Red).bold(),
+            modal_info: Style::new().fg(Color::Blue),
+            modal_warning: Style::new().fg(Color::Magenta),
+            modal_error: Style::new().fg(Color::Red),
+            modal_confirm: Style::new().fg(Color::Blue),
+            diff_added: Style::new().fg(Color::Green),
+            diff_removed: Style::new().fg(Color::Red),
+            diff_hunk: Style::new().fg(Color::Blue),
+            diff_header: Style::new().fg(Color::Black).bold(),
+            diff_property: Style::new().fg(Color::Magenta),
+            diff_added_word: Style::new().fg(Color::White).bg(Color::Green),
+            diff_removed_word: Style::new().fg(Color::White).bg(Color::Red),
+            link: Style::new().fg(Color::Blue).underlined(),
+            syntax: Some("InspiredGitHub"),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            status: status_styles(&[
+                ("M", Color::LightCyan),
+                ("A", Color::LightGreen),
+                ("D", Color::LightRed),
+                ("C", Color::LightRed),
+                ("?", Color::LightYellow),
+                ("!", Color::LightRed),
+                ("I", Color::Gray),
+                ("R", Color::LightCyan),
+                ("X", Color::LightMagenta),
+                ("~", Color::LightMagenta),
+                ("K", Color::LightGreen),
+                ("O", Color::LightRed),
+                ("T", Color::LightRed),
+                ("B", Color::Gray),
+                ("*", Color::LightYellow),
+            ])
+            .into_iter()
+            .map(|(state, style)| (state, style.bold()))
+            .collect(),
+            selected: Style::new().bg(Color::Yellow).fg(Color::Black).bold(),
+            highlight: Style::new().fg(Color::Black).bg(Color::White),
+            matched: Style::new().fg(Color::LightYellow).bold().underlined(),
+            visual: Style::new().bg(Color::Blue).fg(Color::White),
+            info: Style::new().fg(Color::White).bold(),
+            text: Style::new().fg(Color::White),
+            error_text: Style::new().fg(Color::LightRed).bold(),
+            yes: Style::new().fg(Color::LightGreen).bold(),
+            no: Style::new().fg(Color::LightRed).bold(),
+            border_normal: Style::new().fg(Color::White),
+            border_focused: Style::new().fg(Color::Yellow).bold(),
+            border_error: Style::new().fg(Color::LightRed).bold(),
+            modal_info: Style::new().fg(Color::White).bold(),
+            modal_warning: Style::new().fg(Color::Yellow).bold(),
+            modal_error: Style::new().fg(Color::LightRed).bold(),
+            modal_confirm: Style::new().fg(Color::Yellow).bold(),
+            diff_added: Style::new().fg(Color::LightGreen).bold(),
+            diff_removed: Style::new().fg(Color::LightRed).bold(),
+            diff_hunk: Style::new().fg(Color::LightCyan).bold(),
+            diff_header: Style::new().fg(Color::White).bold().underlined(),
+            diff_property: Style::new().fg(Color::LightMagenta).bold(),
+            diff_added_word: Style::new().fg(Color::Black).bg(Color::LightGreen).bold(),
+            diff_removed_word: Style::new().fg(Color::Black).bg(Color::LightRed).bold(),
+            link: Style::new().fg(Color::LightCyan).bold().underlined(),
+            syntax: Some("base16-eighties.dark"),
+        }
+    }
+
+    /// Only text modifiers, for terminals or users that ask for `NO_COLOR`
+    pub fn monochrome() -> Self {
+        Theme {
+            status: HashMap::new(),
+            selected: Style::new().add_modifier(Modifier::REVERSED),
+            highlight: Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            matched: Style::new().underlined(),
+            visual: Style::new().italic().underlined(),
+            info: Style::new(),
+            text: Style::new(),
+            error_text: Style::new().bold(),
+            yes: Style::new().bold(),
+            no: Style::new().bold(),
+            border_normal: Style::new(),
+            border_focused: Style::new().bold(),
+            border_error: Style::new().bold().add_modifier(Modifier::REVERSED),
+            modal_info: Style::new(),
+            modal_warning: Style::new().bold(),
+            modal_error: Style::new().bold(),
+            modal_confirm: Style::new().bold(),
+            diff_added: Style::new().bold(),
+            diff_removed: Style::new().dim(),
+            diff_hunk: Style::new().italic(),
+            diff_header: Style::new().bold().underlined(),
+            diff_property: Style::new().italic(),
+            diff_added_word: Style::new().bold().reversed(),
+            diff_removed_word: Style::new().reversed(),
+            link: Style::new().underlined(),
+            syntax: None,
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "mono" | "monochrome" => Some(Theme::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// `NO_COLOR` wins over the configured theme and its overrides
+    pub fn from_config(config: &ThemeConfig) -> (Self, Vec<String>) {
+        if env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return (Theme::monochrome(), Vec::new());
+        }
+        let mut errors = Vec::new();
+        let mut theme = match config.name.as_deref() {
+            Some(name) => Theme::by_name(name).unwrap_or_else(|| {
+                errors.push(trf("theme.unknown", &[&name]));
+                Theme::dark()
+            }),
+            None => Theme::dark(),
+        };
+        for (state, value) in &config.status {
+            match parse_style(value) {
+                Ok(style) => {
+                    theme.status.insert(state.clone(), style);
+                }
+                Err(e) => errors.push(format!("[theme.status] {}: {}", state, e)),
+            }
+        }
+        for (section, values) in [
+            ("border", &config.border),
+            ("modal", &config.modal),
+            ("ui", &config.ui),
+            ("diff", &config.diff),
+        ] {
+            for (key, value) in values {
+                let slot = match (section, key.as_str()) {
+                    ("border", "normal") => &mut theme.border_normal,
+                    ("border", "focused") => &mut theme.border_focused,
+                    ("border", "error") => &mut theme.border_error,
+                    ("modal", "info") => &mut theme.modal_info,
+                    ("modal", "warning") => &mut theme.modal_warning,
+                    ("modal", "error") => &mut theme.modal_error,
+                    ("modal", "confirm") => &mut theme.modal_confirm,
+                    ("ui", "selected") => &mut theme.selected,
+                    ("ui", "highlight") => &mut theme.highlight,
+                    ("ui", "matched") => &mut theme.matched,
+                    ("ui", "visual") => &mut theme.visual,
+                    ("ui", "info") => &mut theme.info,
+                    ("ui", "text") => &mut theme.text,
+                    ("ui", "error_text") => &mut theme.error_text,
+                    ("ui", "yes") => &mut theme.yes,
+                    ("ui", "no") => &mut theme.no,
+                    ("ui", "link") => &mut theme.link,
+                    ("diff", "added") => &mut theme.diff_added,
+                    ("diff", "removed") => &mut theme.diff_removed,
+                    ("diff", "hunk") => &mut theme.diff_hunk,
+                    ("diff", "header") => &mut theme.diff_header,
+                    ("diff", "property") => &mut theme.diff_property,
+                    ("diff", "added_word") => &mut theme.diff_added_word,
+                    ("diff", "removed_word") => &mut theme.diff_removed_word,
+                    _ => {
+                        errors.push(trf("theme.unknown_key", &[&section, key]));
+                        continue;
+                    }
+                };
+                match parse_style(value) {
+                    Ok(style) => *slot = style,
+                    Err(e) => errors.push(format!("[theme.{}] {}: {}", section, key, e)),
+                }
+            }
+        }
+        (theme, errors)
+    }
+
+    pub fn status_style(&self, state: &str) -> Style {
+        self.status.get(state).copied().unwrap_or_default()
+    }
+}
+
+fn status_styles(colors: &[(&str, Color)]) -> HashMap<String, Style> {
+    colors
+        .iter()
+        .map(|(state, color)| (state.to_string(), Style::new().fg(*color)))
+        .collect()
+}
+
+/// Parses styles like `"blue"`, `"bold #ff8800"` or `"black on yellow"`
+pub fn parse_style(value: &str) -> Result<Style, String> {
+    let mut style = Style::new();
+    let mut words = value.split_whitespace();
+    while let Some(word) = words.next() {
+        style = match word {
+            "bold" => style.bold(),
+            "dim" => style.dim(),
+            "italic" => style.italic(),
+            "underlined" => style.underlined(),
+            "reversed" => style.reversed(),
+            "on" => {
+                let color = words
+                    .next()
+                    .ok_or_else(|| trf("theme.missing_bg", &[&value]))?;
+                style.bg(parse_color(color)?)
+            }
+            color => style.fg(parse_color(color)?),
+        };
+    }
+    Ok(style)
+}
+
+fn parse_color(value: &str) -> Result<Color, String> {
+    Color::from_str(value).map_err(|_| trf("theme.invalid_color", &[&value]))
+}
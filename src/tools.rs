@@ -0,0 +1,190 @@
+use crate::{
+    i18n::trf,
+    svn::{ConflictFiles, SvnClient},
+};
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use tempfile::TempDir;
+
+/// Placeholders appended when the command names none
+const DIFF_FILES: &str = "%base %mine";
+const MERGE_FILES: &str = "%base %mine %theirs %merged";
+
+/// `[tools]` section: external programs for the current file, e.g.
+/// `diff = "meld %base %mine"` or `merge = "kdiff3 %base %mine %theirs -o %merged"`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ToolsConfig {
+    pub diff: Option<String>,
+    /// For conflicted files; the diff tool is used when unset
+    pub merge: Option<String>,
+}
+
+/// A tool command ready to run, and the temporary files it was given
+#[derive(Debug)]
+pub struct ToolLaunch {
+    args: Vec<String>,
+    /// Holds the files made up for the tool, removed with it on drop
+    _temp_dir: Option<TempDir>,
+    /// Merges are checked for their exit status, diff tools report
+    /// differences with it
+    merge: bool,
+}
+
+/// Each word of the command with its placeholders filled in. Splitting
+/// before substituting keeps paths with spaces in one argument.
+fn tool_args(command: &str, defaults: &str, files: &[(&str, &Path)]) -> Vec<String> {
+    let has_placeholder = files.iter().any(|(name, _)| command.contains(name));
+    let command = if has_placeholder {
+        command.to_string()
+    } else {
+        format!("{} {}", command, defaults)
+    };
+    command
+        .split_whitespace()
+        .map(|word| {
+            files.iter().fold(word.to_string(), |word, (name, path)| {
+                word.replace(name, &path.to_string_lossy())
+            })
+        })
+        .collect()
+}
+
+fn temp_path(dir: &TempDir, file: &Path, label: &str) -> PathBuf {
+    // Keeping the extension lets the tool pick its highlighting
+    let name = file.file_name().unwrap_or_default().to_string_lossy();
+    dir.path().join(format!("{}-{}", label, name))
+}
+
+impl ToolLaunch {
+    /// `%base` is the pristine copy and `%mine` the working file. Files
+    /// without a pristine copy and deleted files get an empty file for the
+    /// side that does not exist.
+    pub fn diff(
+        config: &ToolsConfig,
+        svn: &SvnClient,
+        working_copy: &Path,
+        file: &Path,
+        has_base: bool,
+    ) -> Result<Self, String> {
+        let command = config
+            .diff
+            .as_deref()
+            .ok_or_else(|| trf("tools.not_configured", &[&"diff"]))?;
+        // Only this user can enter the directory, nobody can put a link where
+        // the files are written
+        let temp_dir = tempfile::Builder::new()
+            .prefix("rsvn-tool-")
+            .tempdir()
+            .map_err(|e| trf("tools.temp_failed", &[&e]))?;
+        let base = temp_path(&temp_dir, file, "base");
+        if has_base {
+            svn.cat_base(file, &base)?;
+        } else {
+            fs::write(&base, "").map_err(|e| trf("tools.temp_failed", &[&e]))?;
+        }
+        let mut mine = working_copy.join(file);
+        if !mine.exists() {
+            mine = temp_path(&temp_dir, file, "mine");
+            fs::write(&mine, "").map_err(|e| trf("tools.temp_failed", &[&e]))?;
+        }
+        Ok(ToolLaunch {
+            args: tool_args(command, DIFF_FILES, &[("%base", &base), ("%mine", &mine)]),
+            _temp_dir: Some(temp_dir),
+            merge: false,
+        })
+    }
+
+    /// `%base`, `%mine` and `%theirs` are the files svn left for the
+    /// conflict, `%merged` the working file the result goes to
+    pub fn merge(
+        config: &ToolsConfig,
+        working_copy: &Path,
+        file: &Path,
+        conflict: &ConflictFiles,
+    ) -> Result<Self, String> {
+        let command = config
+            .merge
+            .as_deref()
+            .or(config.diff.as_deref())
+            .ok_or_else(|| trf("tools.not_configured", &[&"merge"]))?;
+        let merged = working_copy.join(file);
+        let files = [
+            ("%base", working_copy.join(&conflict.base)),
+            ("%mine", working_copy.join(&conflict.mine)),
+            ("%theirs", working_copy.join(&conflict.theirs)),
+            ("%merged", merged),
+        ];
+        let files: Vec<(&str, &Path)> = files
+            .iter()
+            .map(|(name, path)| (*name, path.as_path()))
+            .collect();
+        Ok(ToolLaunch {
+            args: tool_args(command, MERGE_FILES, &files),
+            _temp_dir: None,
+            merge: true,
+        })
+    }
+
+    /// Waits for the tool. The caller must leave the alternate screen before
+    /// and restore it afterwards, terminal tools like vimdiff draw on it.
+    pub fn run(self, working_copy: &Path) -> Result<(), String> {
+        let Some((program, args)) = self.args.split_first() else {
+            return Err(trf("tools.not_configured", &[&"diff"]));
+        };
+        let command = self.args.join(" ");
+        let status = Command::new(program)
+            .args(args)
+            .current_dir(working_copy)
+            .status()
+            .map_err(|e| trf("tools.launch_failed", &[&command, &e]))?;
+        if self.merge && !status.success() {
+            return Err(trf("tools.merge_status", &[&command, &status]));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_args_fills_placeholders() {
+        let files = [
+            ("%base", Path::new("/tmp/t/base-a b.rs")),
+            ("%mine", Path::new("src/a b.rs")),
+        ];
+        assert_eq!(
+            tool_args("meld --label=%mine %base %mine", DIFF_FILES, &files),
+            vec![
+                "meld",
+                "--label=src/a b.rs",
+                "/tmp/t/base-a b.rs",
+                "src/a b.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn tool_args_appends_defaults_without_placeholders() {
+        let files = [
+            ("%base", Path::new("b")),
+            ("%mine", Path::new("m")),
+            ("%theirs", Path::new("t")),
+            ("%merged", Path::new("out")),
+        ];
+        assert_eq!(
+            tool_args("kdiff3", MERGE_FILES, &files),
+            vec!["kdiff3", "b", "m", "t", "out"]
+        );
+        assert_eq!(
+            tool_args("vimdiff  -R", DIFF_FILES, &files[..2]),
+            vec!["vimdiff", "-R", "b", "m"]
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use crate::{filter::FilterMatch, svn::SvnStatusEntry};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateCounts {
+    pub modified: usize,
+    pub added: usize,
+    pub deleted: usize,
+    pub unversioned: usize,
+}
+
+impl StateCounts {
+    fn count(&mut self, state: &str) {
+        match state {
+            "M" => self.modified += 1,
+            "A" => self.added += 1,
+            "D" => self.deleted += 1,
+            "?" => self.unversioned += 1,
+            _ => {}
+        }
+    }
+}
+
+/// A line of the status pane. In the flat view every row is an `Entry` at depth 0.
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatusRow {
+    Entry {
+        idx: usize,
+        depth: usize,
+        label: String,
+        positions: Vec<usize>,
+    },
+    Dir {
+        path: PathBuf,
+        depth: usize,
+        label: String,
+        collapsed: bool,
+        counts: StateCounts,
+        /// Entries below this directory, including `own`
+        descendants: Vec<usize>,
+        /// The status entry of the directory itself, when svn lists it
+        own: Option<usize>,
+    },
+}
+
+impl StatusRow {
+    /// Entry affected by single-file actions (add, revert, copy path)
+    pub fn entry_idx(&self) -> Option<usize> {
+        match self {
+            StatusRow::Entry { idx, .. } => Some(*idx),
+            StatusRow::Dir { own, .. } => *own,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    own: Option<usize>,
+    dirs: BTreeMap<String, Node>,
+    files: Vec<(String, usize, Vec<usize>)>,
+}
+
+impl Node {
+    fn descendants(&self, out: &mut Vec<usize>) {
+        out.extend(self.own);
+        for node in self.dirs.values() {
+            node.descendants(out);
+        }
+        out.extend(self.files.iter().map(|(_, idx, _)| *idx));
+    }
+}
+
+pub fn flat_rows(entries: &[SvnStatusEntry], visible: &[FilterMatch]) -> Vec<StatusRow> {
+    visible
+        .iter()
+        .filter_map(|row| {
+            entries.get(row.idx).map(|entry| StatusRow::Entry {
+                idx: row.idx,
+                depth: 0,
+                label: entry.file.to_string_lossy().into_owned(),
+                positions: row.positions.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Groups the visible entries by directory. Chains of directories with a single
+/// subdirectory and nothing else are shown as one row (`src/main/java`).
+pub fn tree_rows(
+    entries: &[SvnStatusEntry],
+    visible: &[FilterMatch],
+    collapsed: &HashSet<PathBuf>,
+) -> Vec<StatusRow> {
+    let mut root = Node::default();
+    for row in visible {
+        let Some(entry) = entries.get(row.idx) else {
+            continue;
+        };
+        let components: Vec<String> = entry
+            .file
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let Some((name, parents)) = components.split_last() else {
+            continue;
+        };
+        let mut node = &mut root;
+        for parent in parents {
+            node = node.dirs.entry(parent.clone()).or_default();
+        }
+        // Positions are relative to the whole path, the label only shows the name
+        let offset = entry.file.to_string_lossy().chars().count() - name.chars().count();
+        let positions = row
+            .positions
+            .iter()
+            .filter_map(|p| p.checked_sub(offset))
+            .collect();
+        node.files.push((name.clone(), row.idx, positions));
+    }
+    absorb_dir_entries(&mut root);
+    let mut rows = Vec::new();
+    push_rows(&root, Path::new(""), 0, entries, collapsed, &mut rows);
+    rows
+}
+
+/// Entries that are directories themselves become the `own` entry of their node
+fn absorb_dir_entries(node: &mut Node) {
+    let files = std::mem::take(&mut node.files);
+    for (name, idx, positions) in files {
+        match node.dirs.get_mut(&name) {
+            Some(dir) if dir.own.is_none() => dir.own = Some(idx),
+            _ => node.files.push((name, idx, positions)),
+        }
+    }
+    for dir in node.dirs.values_mut() {
+        absorb_dir_entries(dir);
+    }
+}
+
+fn push_rows(
+    node: &Node,
+    path: &Path,
+    depth: usize,
+    entries: &[SvnStatusEntry],
+    collapsed: &HashSet<PathBuf>,
+    rows: &mut Vec<StatusRow>,
+) {
+    for (name, child) in &node.dirs {
+        let mut child = child;
+        let mut label = name.clone();
+        let mut child_path = path.join(name);
+        while child.own.is_none() && child.files.is_empty() && child.dirs.len() == 1 {
+            let (next_name, next) = child.dirs.iter().next().expect("one subdirectory");
+            label = format!("{}/{}", label, next_name);
+            child_path = child_path.join(next_name);
+            child = next;
+        }
+        let mut descendants = Vec::new();
+        child.descendants(&mut descendants);
+        let mut counts = StateCounts::default();
+        for idx in &descendants {
+            if let Some(entry) = entries.get(*idx) {
+                counts.count(&entry.state);
+            }
+        }
+        let is_collapsed = collapsed.contains(&child_path);
+        rows.push(StatusRow::Dir {
+            path: child_path.clone(),
+            depth,
+            label,
+            collapsed: is_collapsed,
+            counts,
+            descendants,
+            own: child.own,
+        });
+        if !is_collapsed {
+            push_rows(child, &child_path, depth + 1, entries, collapsed, rows);
+        }
+    }
+    for (name, idx, positions) in &node.files {
+        rows.push(StatusRow::Entry {
+            idx: *idx,
+            depth,
+            label: name.clone(),
+            positions: positions.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::filter_entries;
+
+    fn entries(files: &[(&str, &str)]) -> Vec<SvnStatusEntry> {
+        files
+            .iter()
+            .map(|(state, file)| SvnStatusEntry::new(PathBuf::from(file), state.to_string()))
+            .collect()
+    }
+
+    fn sample() -> Vec<SvnStatusEntry> {
+        entries(&[
+            ("M", "README.md"),
+            ("A", "src"),
+            ("M", "src/main.rs"),
+            ("?", "src/ui/draw.rs"),
+            ("D", "src/ui/old.rs"),
+            ("A", "tests/it/deep/case.rs"),
+        ])
+    }
+
+    /// Label and depth of each row, directories marked with a trailing `/`
+    fn outline(rows: &[StatusRow]) -> Vec<(String, usize)> {
+        rows.iter()
+            .map(|row| match row {
+                StatusRow::Entry { label, depth, .. } => (label.clone(), *depth),
+                StatusRow::Dir { label, depth, .. } => (format!("{}/", label), *depth),
+            })
+            .collect()
+    }
+
+    fn dir<'a>(rows: &'a [StatusRow], name: &str) -> &'a StatusRow {
+        rows.iter()
+            .find(|row| matches!(row, StatusRow::Dir { label, .. } if label == name))
+            .expect("directory row")
+    }
+
+    #[test]
+    fn tree_rows_nest_directories_first() {
+        let entries = sample();
+        let rows = tree_rows(&entries, &filter_entries(&entries, ""), &HashSet::new());
+        let expected = [
+            ("src/", 0),
+            ("ui/", 1),
+            ("draw.rs", 2),
+            ("old.rs", 2),
+            ("main.rs", 1),
+            ("tests/it/deep/", 0),
+            ("case.rs", 1),
+            ("README.md", 0),
+        ];
+        let expected: Vec<(String, usize)> = expected
+            .iter()
+            .map(|(label, depth)| (label.to_string(), *depth))
+            .collect();
+        assert_eq!(outline(&rows), expected);
+    }
+
+    #[test]
+    fn tree_rows_count_states_below_each_directory() {
+        let entries = sample();
+        let rows = tree_rows(&entries, &filter_entries(&entries, ""), &HashSet::new());
+        let StatusRow::Dir {
+            counts,
+            descendants,
+            own,
+            ..
+        } = dir(&rows, "src")
+        else {
+            unreachable!();
+        };
+        assert_eq!(
+            *counts,
+            StateCounts {
+                modified: 1,
+                added: 1,
+                deleted: 1,
+                unversioned: 1,
+            }
+        );
+        assert_eq!(*own, Some(1));
+        let mut descendants = descendants.clone();
+        descendants.sort();
+        assert_eq!(descendants, vec![1, 2, 3, 4]);
+        assert_eq!(dir(&rows, "src").entry_idx(), Some(1));
+        assert_eq!(dir(&rows, "tests/it/deep").entry_idx(), None);
+    }
+
+    #[test]
+    fn tree_rows_map_rows_to_entries() {
+        let entries = sample();
+        let rows = tree_rows(&entries, &filter_entries(&entries, ""), &HashSet::new());
+        for row in &rows {
+            if let StatusRow::Entry { idx, label, .. } = row {
+                assert!(entries[*idx].file.ends_with(label), "{} -> {}", label, idx);
+            }
+        }
+        let files: Vec<usize> = rows
+            .iter()
+            .filter_map(|row| match row {
+                StatusRow::Entry { idx, .. } => Some(*idx),
+                StatusRow::Dir { .. } => None,
+            })
+            .collect();
+        assert_eq!(files, vec![3, 4, 2, 5, 0]);
+    }
+
+    #[test]
+    fn collapsed_directories_hide_their_rows() {
+        let entries = sample();
+        let collapsed = HashSet::from([PathBuf::from("src")]);
+        let rows = tree_rows(&entries, &filter_entries(&entries, ""), &collapsed);
+        let labels: Vec<String> = outline(&rows).into_iter().map(|(label, _)| label).collect();
+        assert_eq!(
+            labels,
+            vec!["src/", "tests/it/deep/", "case.rs", "README.md"]
+        );
+        let StatusRow::Dir {
+            collapsed,
+            descendants,
+            ..
+        } = dir(&rows, "src")
+        else {
+            unreachable!();
+        };
+        assert!(collapsed);
+        assert_eq!(descendants.len(), 4);
+    }
+
+    #[test]
+    fn filtered_tree_keeps_match_positions_of_the_name() {
+        let entries = sample();
+        let rows = tree_rows(&entries, &filter_entries(&entries, "draw"), &HashSet::new());
+        assert_eq!(
+            outline(&rows),
+            vec![("src/ui/".to_string(), 0), ("draw.rs".to_string(), 1)]
+        );
+        let StatusRow::Entry { idx, positions, .. } = &rows[1] else {
+            unreachable!();
+        };
+        assert_eq!(*idx, 3);
+        assert_eq!(*positions, vec![0, 1, 2, 3]);
+    }
+}
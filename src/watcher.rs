@@ -0,0 +1,74 @@
+use crate::i18n::trf;
+use notify_debouncer_mini::{
+    DebounceEventResult, Debouncer, new_debouncer,
+    notify::{RecommendedWatcher, RecursiveMode},
+};
+use serde::Deserialize;
+use std::{
+    path::{Component, Path},
+    time::Duration,
+};
+
+const DEFAULT_DEBOUNCE_MS: u64 = 500;
+
+/// `[watch]` section: refresh the status when files in the working copy change
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// On by default
+    pub enabled: Option<bool>,
+    pub debounce_ms: Option<u64>,
+}
+
+impl WatchConfig {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    pub fn debounce(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS))
+    }
+}
+
+/// svn rewrites its own metadata on every command, including `svn status`
+fn is_svn_internal(path: &Path) -> bool {
+    path.components()
+        .any(|component| component == Component::Normal(".svn".as_ref()))
+}
+
+/// Keeps the watch alive; dropping it stops the notifications
+pub struct Watcher {
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+impl std::fmt::Debug for Watcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Watcher")
+    }
+}
+
+impl Watcher {
+    /// Calls `on_change` from the watcher thread once the files under `root`
+    /// stop changing for `delay`
+    pub fn start<F>(root: &Path, delay: Duration, on_change: F) -> Result<Self, String>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let handler = move |result: DebounceEventResult| {
+            if let Ok(events) = result
+                && events.iter().any(|event| !is_svn_internal(&event.path))
+            {
+                on_change();
+            }
+        };
+        let mut debouncer =
+            new_debouncer(delay, handler).map_err(|e| trf("watch.failed", &[&e]))?;
+        debouncer
+            .watcher()
+            .watch(root, RecursiveMode::Recursive)
+            .map_err(|e| trf("watch.failed", &[&e]))?;
+        Ok(Watcher {
+            _debouncer: debouncer,
+        })
+    }
+}